@@ -4,12 +4,15 @@ use std::vec::Vec;
 
 use clap::{App, ArgMatches, SubCommand};
 use reqwest::RequestBuilder;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::super::super::args::fqdn::add_fqdn_options;
 use super::super::super::command_handler::GandiSubCommandHandler;
 use super::super::super::config::Configuration;
 use super::super::super::display::{add_subcommand_options, print_line};
+use super::super::super::formatter::string_enum;
+use super::super::super::formatter::Extra;
 
 macro_rules! ROUTE {
     () => {
@@ -17,28 +20,54 @@ macro_rules! ROUTE {
     };
 }
 
-/// Contact information
-#[derive(Debug, Serialize, Deserialize)]
+string_enum! {
+    /// Type of a DNS record
+    pub enum RecordType {
+        A => "A",
+        Aaaa => "AAAA",
+        Alias => "ALIAS",
+        Caa => "CAA",
+        Cds => "CDS",
+        Cname => "CNAME",
+        Dname => "DNAME",
+        Ds => "DS",
+        Key => "KEY",
+        Loc => "LOC",
+        Mx => "MX",
+        Ns => "NS",
+        OpenPgpKey => "OPENPGPKEY",
+        Ptr => "PTR",
+        Spf => "SPF",
+        Srv => "SRV",
+        SshFp => "SSHFP",
+        Tlsa => "TLSA",
+        Txt => "TXT",
+        Wks => "WKS",
+    }
+}
+
+/// DNS record set information
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Record {
     /// URL for the record
-    rrset_href: String,
+    pub rrset_href: String,
     /// Time to live of the record
-    rrset_ttl: usize,
+    pub rrset_ttl: usize,
     /// Name of the record
-    rrset_name: String,
+    pub rrset_name: String,
     /// One of: "A", "AAAA", "ALIAS", "CAA", "CDS", "CNAME", "DNAME", "DS", "KEY", "LOC", "MX", "NS", "OPENPGPKEY", "PTR", "SPF", "SRV", "SSHFP", "TLSA", "TXT", "WKS"
-    rrset_type: String,
+    pub rrset_type: RecordType,
     /// A list of values for this record
-    rrset_values: Vec<String>,
+    pub rrset_values: Vec<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-const SUB_COMMAND: &'static str = "records";
-
 pub struct DnsRecordsListCommand {}
 
 impl GandiSubCommandHandler for DnsRecordsListCommand {
-    const COMMAND_GROUP: &'static str = "list";
-    const COMMAND: &'static str = "dns";
+    const PATH: &'static [&'static str] = &["list", "dns", "records"];
+    const READ_ONLY: bool = true;
     type Item = Vec<Record>;
 
     /// Create the route
@@ -67,34 +96,18 @@ impl GandiSubCommandHandler for DnsRecordsListCommand {
                         "{} {} IN {} {}",
                         record.rrset_name.as_str(),
                         record.rrset_ttl,
-                        record.rrset_type.as_str(),
+                        record.rrset_type,
                         val
                     )
                     .as_str(),
                 );
-
-            } 
-        }
-    }
-
-    /// Check if the operation in case the matches is processable.
-    fn can_handle<'a>(matches: &'a ArgMatches) -> Option<&'a ArgMatches<'a>> {
-        if matches.is_present(Self::COMMAND_GROUP) {
-            let subcommand = matches.subcommand_matches(Self::COMMAND_GROUP).unwrap();
-            if subcommand.is_present(Self::COMMAND) {
-                let subcommand = subcommand.subcommand_matches(Self::COMMAND).unwrap();
-                if subcommand.is_present(SUB_COMMAND) {
-                    let params = subcommand.subcommand_matches(SUB_COMMAND).unwrap();
-                    return Some(params);
-                }
             }
         }
-        None
     }
 
     /// Create the clap subcommand with its arguments.
     fn subcommand<'a, 'b>() -> App<'a, 'b> {
-        let subcommand = SubCommand::with_name(SUB_COMMAND);
+        let subcommand = SubCommand::with_name(Self::name());
         //let subcommand = add_pagination_options(subcommand);
         //let subcommand = add_sharing_id_options(subcommand);
         let subcommand = add_fqdn_options(subcommand);
@@ -5,13 +5,17 @@ use std::vec::Vec;
 use chrono::{DateTime, Utc};
 use clap::{App, ArgMatches, SubCommand};
 use reqwest::RequestBuilder;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::super::super::args::fqdn::add_fqdn_options;
 use super::super::super::command_handler::GandiSubCommandHandler;
 use super::super::super::config::Configuration;
-use super::super::super::display::{add_subcommand_options, print_info};
-use super::super::super::formatter::date_formatter_z;
+use super::super::super::display::{
+    add_subcommand_options, print_date, print_empty_line, print_info,
+};
+use super::super::super::formatter::date_formatter;
+use super::super::super::formatter::Extra;
 
 macro_rules! ROUTE {
     () => {
@@ -19,27 +23,26 @@ macro_rules! ROUTE {
     };
 }
 
-/// Contact information
-#[derive(Debug, Serialize, Deserialize)]
+/// LiveDNS snapshot information
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Snapshot {
     /// Identifier of the snapshot
-    id: String,
+    pub id: String,
     /// Creation date of the snapshot (UTC)
-    #[serde(with = "date_formatter_z")]
-    created_at: DateTime<Utc>,
+    #[serde(with = "date_formatter")]
+    #[schemars(schema_with = "date_formatter::schema")]
+    pub created_at: DateTime<Utc>,
     /// name of the snapshot
-    name: String,
-    // /// URL of the snapshot
-    //snapshot_href: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-const SUB_COMMAND: &'static str = "snapshot";
-
 pub struct DnsSnapshotsListCommand {}
 
 impl GandiSubCommandHandler for DnsSnapshotsListCommand {
-    const COMMAND_GROUP: &'static str = "list";
-    const COMMAND: &'static str = "dns";
+    const PATH: &'static [&'static str] = &["list", "dns", "snapshot"];
+    const READ_ONLY: bool = true;
     type Item = Vec<Snapshot>;
 
     /// Create the route
@@ -62,31 +65,16 @@ impl GandiSubCommandHandler for DnsSnapshotsListCommand {
     /// Display the records important data
     fn display_human_result(items: Self::Item) {
         for snapshot in items {
-            print!("");
+            print_empty_line();
             print_info("Id:", snapshot.id.as_str());
             print_info("Name:", snapshot.name.as_str());
-            print_info("Created at:", snapshot.created_at.to_rfc2822().as_str());
-        }
-    }
-
-    /// Check if the operation in case the matches is processable.
-    fn can_handle<'a>(matches: &'a ArgMatches) -> Option<&'a ArgMatches<'a>> {
-        if matches.is_present(Self::COMMAND_GROUP) {
-            let subcommand = matches.subcommand_matches(Self::COMMAND_GROUP).unwrap();
-            if subcommand.is_present(Self::COMMAND) {
-                let subcommand = subcommand.subcommand_matches(Self::COMMAND).unwrap();
-                if subcommand.is_present(SUB_COMMAND) {
-                    let params = subcommand.subcommand_matches(SUB_COMMAND).unwrap();
-                    return Some(params);
-                }
-            }
+            print_date("Created at:", Some(&snapshot.created_at));
         }
-        None
     }
 
     /// Create the clap subcommand with its arguments.
     fn subcommand<'a, 'b>() -> App<'a, 'b> {
-        let subcommand = SubCommand::with_name(SUB_COMMAND);
+        let subcommand = SubCommand::with_name(Self::name());
         //let subcommand = add_pagination_options(subcommand);
         //let subcommand = add_sharing_id_options(subcommand);
         let subcommand = add_fqdn_options(subcommand);
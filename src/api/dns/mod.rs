@@ -1,6 +1,3 @@
-/// The command handle for list
-pub mod list;
-
 /// [dns records list](https://api.gandi.net/docs/livedns/#get-v5-livedns-domains-fqdn-records) route binding
 pub mod list_records;
 
@@ -1,24 +1,63 @@
 //! [Check domain availability](https://api.gandi.net/docs/domains/#get-v5-domain-check) route binding
+use std::fmt::{self, Display};
 use std::vec::Vec;
 
 use chrono::{DateTime, Utc};
 use clap::{App, ArgMatches, SubCommand};
 use reqwest::RequestBuilder;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::super::super::args::fqdn::add_fqdn_options;
 use super::super::super::args::sharing_id::{
     add_subcommand_options as add_sharing_id_options, SharingSpace,
 };
-use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::command_handler::{send, GandiSubCommandHandler};
 use super::super::super::config::Configuration;
-use super::super::super::display::{add_subcommand_options, print_info};
+use super::super::super::display::{add_subcommand_options, print_date, print_info};
+use super::super::super::errors::{GandiError, GandiResult};
 use super::super::super::formatter::date_formatter;
+use super::super::super::formatter::string_enum;
+use super::super::super::formatter::Extra;
 
 pub const ROUTE: &str = "/v5/domain/check";
 
+string_enum! {
+    /// Availability of a product
+    pub enum ProductStatus {
+        Available => "available",
+        AvailableReserved => "available_reserved",
+        AvailablePreorder => "available_preorder",
+        Unavailable => "unavailable",
+        UnavailablePremium => "unavailable_premium",
+        UnavailableRestricted => "unavailable_restricted",
+        Reserved => "reserved",
+        Pending => "pending",
+        Error => "error",
+    }
+}
+
+string_enum! {
+    /// Operation the prices of a product apply to
+    pub enum Process {
+        Create => "create",
+        Renew => "renew",
+        Transfer => "transfer",
+        Restore => "restore",
+        ChangeOwner => "change_owner",
+    }
+}
+
+string_enum! {
+    /// Tier of a price, premium names have their own prices
+    pub enum PriceTier {
+        Standard => "standard",
+        Premium => "premium",
+    }
+}
+
 /// Price tax
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 struct Tax {
     /// name of the tax
     name: String,
@@ -27,30 +66,38 @@ struct Tax {
     type_: String,
     /// tax rate in percent
     rate: f32,
+    #[serde(flatten)]
+    extra: Extra,
 }
 
 /// Options Product prices
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 struct PriceOptions {
     /// registration period: sunrise, landrush, golive
     #[serde(skip_serializing_if = "Option::is_none")]
     period: Option<String>, // badly documented
+    #[serde(flatten)]
+    extra: Extra,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 struct Period {
     /// name of the tax
     name: String,
     /// starting date
     #[serde(with = "date_formatter")]
+    #[schemars(schema_with = "date_formatter::schema")]
     starts_at: DateTime<Utc>,
     /// ending date
     #[serde(with = "date_formatter")]
+    #[schemars(schema_with = "date_formatter::schema")]
     ends_at: DateTime<Utc>,
+    #[serde(flatten)]
+    extra: Extra,
 }
 
 /// Product prices
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 struct Price {
     /// minimum duration for the price
     min_duration: usize,
@@ -66,18 +113,23 @@ struct Price {
     price_before_taxes: f32,
     /// price taxes included
     price_after_taxes: f32,
+    /// tier of the price
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    tier: Option<PriceTier>,
 
     /// options of what ?
     options: PriceOptions,
+    #[serde(flatten)]
+    extra: Extra,
 }
 
 /// Product prices wrapped by process and status
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 struct Product {
     /// Status prices are exposed
-    process: Option<String>, // marked as optional ?
+    process: Option<Process>, // marked as optional ?
     /// Status prices are exposed
-    status: String,
+    status: ProductStatus,
     /// the fqdn
     name: String,
 
@@ -88,10 +140,12 @@ struct Product {
     taxes: Vec<Tax>,
     #[serde(skip_serializing_if = "Option::is_none")]
     period: Option<Vec<Period>>,
+    #[serde(flatten)]
+    extra: Extra,
 }
 
 /// Domain Availability Check Format, returned by the API
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct DomainCheck {
     /// currency prices are exposed
     currency: String,
@@ -100,14 +154,278 @@ pub struct DomainCheck {
     /// products
     #[serde(skip_serializing_if = "Option::is_none")]
     products: Option<Vec<Product>>,
+    #[serde(flatten)]
+    extra: Extra,
+}
+
+impl ProductStatus {
+    /// The product can be registered
+    pub fn is_available(&self) -> bool {
+        matches!(
+            self,
+            ProductStatus::Available
+                | ProductStatus::AvailableReserved
+                | ProductStatus::AvailablePreorder
+        )
+    }
+}
+
+/// Availability of a checked domain, the categories of the bulk reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Availability {
+    Available,
+    /// a premium name, sold at its own price if available
+    Premium,
+    Taken,
+    /// the domain could not be checked
+    Failed,
+}
+
+impl Availability {
+    /// Every categories, in their display order
+    pub const ALL: [Availability; 4] = [
+        Availability::Available,
+        Availability::Premium,
+        Availability::Taken,
+        Availability::Failed,
+    ];
+
+    /// Name of the category
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Availability::Available => "available",
+            Availability::Premium => "premium",
+            Availability::Taken => "taken",
+            Availability::Failed => "failed",
+        }
+    }
+}
+
+/// Price of a process on a domain, for a duration
+#[derive(Debug)]
+pub struct Quote {
+    pub fqdn: String,
+    pub process: Process,
+    pub duration: usize,
+    pub duration_unit: String,
+    /// price per duration unit, taxes included
+    pub unit_price: f32,
+    pub currency: String,
+}
+
+impl Quote {
+    /// Price for the whole duration, taxes included
+    pub fn total(&self) -> f32 {
+        self.unit_price * self.duration as f32
+    }
+}
+
+/// Human format of the quote, e.g. `create example.org for 1y: 15.50 EUR`
+impl Display for Quote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} for {}{}: {:.2} {}",
+            self.process,
+            self.fqdn,
+            self.duration,
+            self.duration_unit,
+            self.total(),
+            self.currency
+        )
+    }
+}
+
+/// Availability and cheapest price of a domain, a row of the bulk reports.
+///
+/// Every field is serialized, even unset, to keep the CSV columns.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CheckReport {
+    pub fqdn: String,
+    pub status: Option<ProductStatus>,
+    pub process: Option<Process>,
+    /// cheapest price per duration unit, taxes included
+    pub price: Option<f32>,
+    pub duration_unit: Option<String>,
+    pub currency: Option<String>,
+    /// registration period of the price: sunrise, landrush, golive...
+    pub period: Option<String>,
+    /// tier of the price, e.g. premium
+    pub tier: Option<PriceTier>,
+    /// launch phase of the tld, running or upcoming, e.g. sunrise
+    pub phase: Option<String>,
+    #[serde(default, with = "date_formatter::optional")]
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub phase_starts_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "date_formatter::optional")]
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub phase_ends_at: Option<DateTime<Utc>>,
+    /// the reason the domain has not been checked
+    pub error: Option<String>,
+}
+
+impl CheckReport {
+    /// Report of a domain that could not be checked
+    pub fn failed(fqdn: &str, error: &GandiError) -> Self {
+        CheckReport {
+            fqdn: fqdn.to_string(),
+            status: None,
+            process: None,
+            price: None,
+            duration_unit: None,
+            currency: None,
+            period: None,
+            tier: None,
+            phase: None,
+            phase_starts_at: None,
+            phase_ends_at: None,
+            error: Some(format!("{}", error)),
+        }
+    }
+
+    /// The domain is a premium name, sold at a premium price if available
+    pub fn is_premium(&self) -> bool {
+        self.status == Some(ProductStatus::UnavailablePremium)
+            || self.tier == Some(PriceTier::Premium)
+    }
+
+    /// Category of the domain in the bulk reports
+    pub fn availability(&self) -> Availability {
+        match &self.status {
+            _ if self.error.is_some() => Availability::Failed,
+            _ if self.is_premium() => Availability::Premium,
+            Some(status) if status.is_available() => Availability::Available,
+            _ => Availability::Taken,
+        }
+    }
+}
+
+impl DomainCheck {
+    /// Report the availability and the cheapest price of the process.
+    pub fn report(self, fqdn: &str, process: Process) -> CheckReport {
+        let currency = self.currency;
+        let product = self
+            .products
+            .unwrap_or_default()
+            .into_iter()
+            .find(|product| {
+                product.process.is_none() || product.process.as_ref() == Some(&process)
+            });
+        let mut report = CheckReport {
+            fqdn: fqdn.to_string(),
+            status: None,
+            process: Some(process),
+            price: None,
+            duration_unit: None,
+            currency: None,
+            period: None,
+            tier: None,
+            phase: None,
+            phase_starts_at: None,
+            phase_ends_at: None,
+            error: None,
+        };
+        if let Some(product) = product {
+            report.status = Some(product.status);
+            let now = Utc::now();
+            if let Some(phase) = product
+                .period
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|phase| phase.ends_at > now)
+                .min_by_key(|phase| phase.starts_at)
+            {
+                report.phase = Some(phase.name);
+                report.phase_starts_at = Some(phase.starts_at);
+                report.phase_ends_at = Some(phase.ends_at);
+            }
+            let cheapest = product
+                .prices
+                .unwrap_or_default()
+                .into_iter()
+                .min_by(|a, b| a.price_after_taxes.total_cmp(&b.price_after_taxes));
+            if let Some(price) = cheapest {
+                report.price = Some(price.price_after_taxes);
+                report.duration_unit = Some(price.duration_unit);
+                report.currency = Some(currency);
+                report.period = price.options.period;
+                report.tier = price.tier;
+            }
+        }
+        report
+    }
+
+    /// Quote the process for the duration, the product must be available.
+    pub fn quote(self, fqdn: &str, process: Process, duration: usize) -> GandiResult<Quote> {
+        let currency = self.currency;
+        let product = self
+            .products
+            .unwrap_or_default()
+            .into_iter()
+            .find(|product| product.process.is_none() || product.process.as_ref() == Some(&process))
+            .ok_or_else(|| {
+                GandiError::Unavailable(fqdn.to_string(), format!("no {} product", process))
+            })?;
+        if !product.status.is_available() {
+            return Err(GandiError::Unavailable(
+                fqdn.to_string(),
+                product.status.to_string(),
+            ));
+        }
+        let price = product
+            .prices
+            .unwrap_or_default()
+            .into_iter()
+            .find(|price| price.min_duration <= duration && duration <= price.max_duration)
+            .ok_or_else(|| {
+                GandiError::Unavailable(
+                    fqdn.to_string(),
+                    format!("no {} price for a duration of {}", process, duration),
+                )
+            })?;
+        Ok(Quote {
+            fqdn: fqdn.to_string(),
+            process,
+            duration,
+            duration_unit: price.duration_unit,
+            unit_price: price.price_after_taxes,
+            currency,
+        })
+    }
+}
+
+/// Check the domain and quote the process for the duration.
+pub fn quote(
+    config: &Configuration,
+    fqdn: &str,
+    sharing_space: &SharingSpace,
+    process: Process,
+    duration: usize,
+) -> GandiResult<Quote> {
+    let req = build_check_req(config, fqdn, sharing_space, &process);
+    let check: DomainCheck = send(req)?.json()?;
+    check.quote(fqdn, process, duration)
+}
+
+/// Build the check request of the domain, with the prices of the process only.
+pub fn build_check_req(
+    config: &Configuration,
+    fqdn: &str,
+    sharing_space: &SharingSpace,
+    process: &Process,
+) -> RequestBuilder {
+    let req = config
+        .build_req(ROUTE)
+        .query(&[("name", fqdn), ("processes", process.as_str())]);
+    sharing_space.build_req(req)
 }
 
 /// implement the "check domain" subcommand
 pub struct DomainCheckCommand {}
 
 impl GandiSubCommandHandler for DomainCheckCommand {
-    const COMMAND_GROUP: &'static str = "check";
-    const COMMAND: &'static str = "domain";
+    const PATH: &'static [&'static str] = &["check", "domain"];
+    const READ_ONLY: bool = true;
     type Item = DomainCheck;
 
     /// Create the route
@@ -121,16 +439,20 @@ impl GandiSubCommandHandler for DomainCheckCommand {
     /// Display the domain important data
     fn display_human_result(item: Self::Item) {
         let golive = "golive".to_string();
-        let missing_process = "???".to_string();
+        let missing_process = "???";
         //println!("Check: {:?}", check);
 
         let products = item.products.unwrap_or(vec![]);
         for product in products {
-            if product.status != "available" {
+            if product.status != ProductStatus::Available {
                 print_info(
                     format!(
                         "{} {}",
-                        product.process.as_ref().unwrap_or(&missing_process),
+                        product
+                            .process
+                            .as_ref()
+                            .map(Process::as_str)
+                            .unwrap_or(missing_process),
                         product.name
                     )
                     .as_str(),
@@ -142,7 +464,11 @@ impl GandiSubCommandHandler for DomainCheckCommand {
                     print_info(
                         format!(
                             "{} {} {} {}",
-                            product.process.as_ref().unwrap_or(&missing_process),
+                            product
+                                .process
+                                .as_ref()
+                                .map(Process::as_str)
+                                .unwrap_or(missing_process),
                             product.name,
                             format!(
                                 "{}{}->{}{}",
@@ -158,14 +484,98 @@ impl GandiSubCommandHandler for DomainCheckCommand {
                     );
                 }
             }
+            for period in product.period.unwrap_or(vec![]) {
+                print_date(
+                    format!("{} {} starts at", period.name, product.name).as_str(),
+                    Some(&period.starts_at),
+                );
+                print_date(
+                    format!("{} {} ends at", period.name, product.name).as_str(),
+                    Some(&period.ends_at),
+                );
+            }
         }
     }
 
     /// Create the clap subcommand with its arguments.
     fn subcommand<'a, 'b>() -> App<'a, 'b> {
-        let subcommand = SubCommand::with_name(Self::COMMAND);
+        let subcommand = SubCommand::with_name(Self::name());
         let subcommand = add_fqdn_options(subcommand);
         let subcommand = add_sharing_id_options(subcommand);
         add_subcommand_options(subcommand)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(status: &str, tier: Option<&str>) -> CheckReport {
+        let price = serde_json::json!({
+            "min_duration": 1,
+            "max_duration": 10,
+            "duration_unit": "y",
+            "price_before_taxes": 10.0,
+            "price_after_taxes": 12.0,
+            "type": tier,
+            "options": {"period": "golive"},
+        });
+        let check: DomainCheck = serde_json::from_value(serde_json::json!({
+            "currency": "EUR",
+            "grid": "A",
+            "products": [{
+                "process": "create",
+                "status": status,
+                "name": "example.com",
+                "prices": [price],
+                "taxes": [],
+            }],
+        }))
+        .unwrap();
+        check.report("example.com", Process::Create)
+    }
+
+    #[test]
+    fn test_availability() {
+        let expected = [
+            ("available", Availability::Available),
+            ("available_reserved", Availability::Available),
+            ("available_preorder", Availability::Available),
+            ("unavailable", Availability::Taken),
+            ("unavailable_premium", Availability::Premium),
+            ("unavailable_restricted", Availability::Taken),
+            ("reserved", Availability::Taken),
+            ("pending", Availability::Taken),
+            ("error", Availability::Taken),
+            ("unknown_status", Availability::Taken),
+        ];
+        assert_eq!(ProductStatus::VALUES.len() + 1, expected.len());
+        for (status, availability) in expected.iter() {
+            assert_eq!(
+                report(status, None).availability(),
+                *availability,
+                "{}",
+                status
+            );
+            let standard = report(status, Some("standard"));
+            assert_eq!(standard.availability(), *availability, "{}", status);
+        }
+    }
+
+    #[test]
+    fn test_availability_premium_price() {
+        let premium = report("available", Some("premium"));
+        assert_eq!(premium.tier, Some(PriceTier::Premium));
+        assert!(premium.is_premium());
+        assert_eq!(premium.availability(), Availability::Premium);
+        assert!(!report("available", Some("standard")).is_premium());
+        assert!(!report("available", None).is_premium());
+    }
+
+    #[test]
+    fn test_availability_failed() {
+        let error = GandiError::Unavailable("example.com".to_string(), "down".to_string());
+        let failed = CheckReport::failed("example.com", &error);
+        assert_eq!(failed.availability(), Availability::Failed);
+    }
+}
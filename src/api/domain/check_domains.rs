@@ -0,0 +1,314 @@
+//! Bulk [check of domains availability](https://api.gandi.net/docs/domains/#get-v5-domain-check) binding
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::{App, Arg, ArgGroup, ArgMatches, SubCommand};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{RequestBuilder, StatusCode};
+
+use super::super::super::args::sharing_id::{
+    add_subcommand_options as add_sharing_id_options, SharingSpace,
+};
+use super::super::super::command_handler::{check_status, GandiSubCommandHandler};
+use super::super::super::config::Configuration;
+use super::super::super::display::{add_subcommand_options, print_table};
+use super::super::super::errors::{GandiError, GandiResult};
+use super::check::{build_check_req, Availability, CheckReport, DomainCheck, Process, ROUTE};
+
+/// Fields the reports can be sorted by, prefix with `-` to reverse.
+pub const SORT_BY_FIELDS: [&str; 4] = ["fqdn", "-fqdn", "price", "-price"];
+
+/// Number of attempts of a check throttled by the API
+const MAX_ATTEMPTS: usize = 5;
+
+/// Delay before retrying a throttled check, if the API does not tell
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Pause shared by the workers, the API asks for it when they are throttled
+struct Throttle {
+    until: Mutex<Instant>,
+}
+
+impl Throttle {
+    /// Wait for the end of the pause, if any
+    fn wait(&self) {
+        let until = *self.until.lock().unwrap();
+        let now = Instant::now();
+        if until > now {
+            thread::sleep(until - now);
+        }
+    }
+
+    /// Pause every workers for the delay
+    fn pause(&self, delay: Duration) {
+        let mut until = self.until.lock().unwrap();
+        *until = (*until).max(Instant::now() + delay);
+    }
+}
+
+/// Delay asked by a throttled response, in seconds
+fn retry_after(headers: &HeaderMap) -> Duration {
+    headers
+        .get(RETRY_AFTER)
+        .and_then(|delay| delay.to_str().ok())
+        .and_then(|delay| delay.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RETRY_AFTER)
+}
+
+/// Check a domain, the throttled requests are retried after the delay of the API.
+fn check(
+    config: &Configuration,
+    fqdn: &str,
+    sharing_space: &SharingSpace,
+    process: &Process,
+    throttle: &Throttle,
+) -> GandiResult<DomainCheck> {
+    let mut attempts = 1;
+    loop {
+        throttle.wait();
+        let resp = build_check_req(config, fqdn, sharing_space, process).send()?;
+        if resp.status() == StatusCode::TOO_MANY_REQUESTS && attempts < MAX_ATTEMPTS {
+            throttle.pause(retry_after(resp.headers()));
+            attempts += 1;
+            continue;
+        }
+        let item = check_status(resp)?.json()?;
+        return Ok(item);
+    }
+}
+
+/// Check the domains with `jobs` concurrent requests, the reports are in
+/// the order of the domains.
+pub fn check_all(
+    config: &Configuration,
+    fqdns: &[String],
+    sharing_space: &SharingSpace,
+    process: Process,
+    jobs: usize,
+) -> Vec<CheckReport> {
+    let next = AtomicUsize::new(0);
+    let throttle = Throttle {
+        until: Mutex::new(Instant::now()),
+    };
+    let reports: Mutex<Vec<Option<CheckReport>>> = Mutex::new(fqdns.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(fqdns.len()) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::SeqCst);
+                let fqdn = match fqdns.get(idx) {
+                    Some(fqdn) => fqdn,
+                    None => break,
+                };
+                let report = match check(config, fqdn, sharing_space, &process, &throttle) {
+                    Ok(check) => check.report(fqdn, process.clone()),
+                    Err(err) => CheckReport::failed(fqdn, &err),
+                };
+                reports.lock().unwrap()[idx] = Some(report);
+            });
+        }
+    });
+    reports
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Sort the reports by a field of `SORT_BY_FIELDS`, the domains without
+/// price are kept last.
+pub fn sort_reports(reports: &mut [CheckReport], sort_by: &str) {
+    match sort_by.trim_start_matches('-') {
+        "price" => reports.sort_by(|a, b| match (a.price, b.price) {
+            (Some(a), Some(b)) if sort_by.starts_with('-') => b.total_cmp(&a),
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        }),
+        _ if sort_by.starts_with('-') => reports.sort_by(|a, b| b.fqdn.cmp(&a.fqdn)),
+        _ => reports.sort_by(|a, b| a.fqdn.cmp(&b.fqdn)),
+    }
+}
+
+/// Read the lines of a file, or of stdin for `-`. Blank lines and `#`
+/// comments are skipped, the duplicates are removed.
+pub fn read_lines(path: &str) -> GandiResult<Vec<String>> {
+    let reader: Box<dyn BufRead> = if path == "-" {
+        Box::new(BufReader::new(io::stdin()))
+    } else {
+        Box::new(BufReader::new(File::open(path)?))
+    };
+    let mut lines: Vec<String> = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line
+            .split('#')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        if !line.is_empty() && !lines.contains(&line) {
+            lines.push(line);
+        }
+    }
+    Ok(lines)
+}
+
+/// Parse the number of concurrent checks
+pub fn parse_jobs(jobs: &str) -> Result<usize, String> {
+    match jobs.parse::<usize>() {
+        Ok(jobs) if (1..=32).contains(&jobs) => Ok(jobs),
+        _ => Err(format!("invalid number of jobs {}, expected 1 to 32", jobs)),
+    }
+}
+
+/// Price of the report in the human format, e.g. `15.50/y`
+pub fn price_label(report: &CheckReport) -> String {
+    match report.price {
+        Some(price) => format!(
+            "{:.2}/{}",
+            price,
+            report.duration_unit.as_deref().unwrap_or_default()
+        ),
+        None => String::new(),
+    }
+}
+
+/// Status of the report in the human format, the error if it failed
+pub fn status_label(report: &CheckReport) -> String {
+    match (&report.error, &report.status) {
+        (Some(error), _) => format!("error: {}", error),
+        (None, Some(status))
+            if status.is_available() && report.availability() == Availability::Premium =>
+        {
+            format!("{} (premium)", status)
+        }
+        (None, Some(status)) => status.to_string(),
+        (None, None) => "unknown".to_string(),
+    }
+}
+
+/// Check the reports, once displayed, the command fails if a check failed
+pub fn failures(reports: &[CheckReport]) -> GandiResult<()> {
+    let failed = reports
+        .iter()
+        .filter(|report| report.error.is_some())
+        .count();
+    if failed > 0 {
+        Err(GandiError::BulkError(failed, reports.len()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Add the options of the bulk checks to the clap subcommand
+pub fn add_bulk_options<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    let app = app
+        .arg(
+            Arg::with_name("JOBS")
+                .short("j")
+                .long("jobs")
+                .takes_value(true)
+                .default_value("4")
+                .validator(|jobs| parse_jobs(&jobs).map(|_| ()))
+                .help("Number of concurrent checks"),
+        )
+        .arg(
+            Arg::with_name("SORT_BY")
+                .long("sort-by")
+                .takes_value(true)
+                .allow_hyphen_values(true)
+                .possible_values(&SORT_BY_FIELDS)
+                .help("Sort the report, prefix the field with '-' to reverse the order"),
+        );
+    let app = add_sharing_id_options(app);
+    add_subcommand_options(app)
+}
+
+/// Implement the "check domains" subcommand
+pub struct DomainsCheckCommand {}
+
+impl GandiSubCommandHandler for DomainsCheckCommand {
+    const PATH: &'static [&'static str] = &["check", "domains"];
+    const READ_ONLY: bool = true;
+    // many requests, see `process`
+    const WATCH: bool = false;
+    type Item = Vec<CheckReport>;
+
+    /// Create the route, the domains are added by `process`
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        SharingSpace::from(params).build_req(config.build_req(ROUTE))
+    }
+
+    /// Check every domains concurrently, and report their cheapest registration
+    fn process(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let mut fqdns: Vec<String> = params
+            .values_of("FQDNS")
+            .map(|fqdns| fqdns.map(str::to_lowercase).collect())
+            .unwrap_or_default();
+        if let Some(path) = params.value_of("FROM") {
+            fqdns.extend(read_lines(path)?);
+        }
+        // checked by the clap validator
+        let jobs = parse_jobs(params.value_of("JOBS").unwrap()).unwrap();
+        let sharing_space = SharingSpace::from(params);
+        let mut reports = check_all(config, &fqdns, &sharing_space, Process::Create, jobs);
+        if let Some(sort_by) = params.value_of("SORT_BY") {
+            sort_reports(&mut reports, sort_by);
+        }
+        let result = failures(&reports);
+        Self::output(reports, &HeaderMap::new(), params)?;
+        result
+    }
+
+    /// Display the report in a table
+    fn display_human_result(reports: Self::Item) {
+        let rows: Vec<Vec<String>> = reports
+            .iter()
+            .map(|report| {
+                vec![
+                    report.fqdn.clone(),
+                    status_label(report),
+                    report
+                        .process
+                        .as_ref()
+                        .map(|process| process.to_string())
+                        .unwrap_or_default(),
+                    price_label(report),
+                    report.currency.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print_table(&["FQDN", "STATUS", "PROCESS", "PRICE", "CURRENCY"], &rows);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Check the availability and the registration price of many domains")
+            .arg(
+                Arg::with_name("FQDNS")
+                    .index(1)
+                    .multiple(true)
+                    .help("domain names to check"),
+            )
+            .arg(
+                Arg::with_name("FROM")
+                    .long("from")
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .help("File of domain names to check, one per line, - for stdin"),
+            )
+            .group(
+                ArgGroup::with_name("DOMAINS")
+                    .args(&["FQDNS", "FROM"])
+                    .multiple(true)
+                    .required(true),
+            );
+        add_bulk_options(subcommand)
+    }
+}
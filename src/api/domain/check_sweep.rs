@@ -0,0 +1,164 @@
+//! Check a label on every [tlds](https://api.gandi.net/docs/domains/#get-v5-domain-tlds) binding
+use clap::{App, Arg, ArgMatches, SubCommand};
+use reqwest::header::HeaderMap;
+use reqwest::RequestBuilder;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::super::super::args::sharing_id::SharingSpace;
+use super::super::super::command_handler::{send, GandiSubCommandHandler};
+use super::super::super::config::Configuration;
+use super::super::super::display::{format_date, print_empty_line, print_info, print_table};
+use super::super::super::errors::GandiResult;
+use super::super::super::formatter::Extra;
+use super::check::{Availability, CheckReport, Process};
+use super::check_domains::{
+    add_bulk_options, check_all, failures, parse_jobs, price_label, read_lines, sort_reports,
+    status_label,
+};
+
+pub const ROUTE: &str = "/v5/domain/tlds";
+
+/// Top level domain of the catalogue
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Tld {
+    /// the tld, without the leading dot
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Check a label of a domain name, e.g. `example`
+fn check_label(label: &str) -> Result<(), String> {
+    let valid = !label.is_empty()
+        && label.len() <= 63
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label.chars().all(|c| c.is_alphanumeric() || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("invalid label {}, expected e.g. example", label))
+    }
+}
+
+/// Fetch the tlds of the Gandi catalogue
+fn fetch_tlds(config: &Configuration, sharing_space: &SharingSpace) -> GandiResult<Vec<String>> {
+    let tlds: Vec<Tld> = send(sharing_space.build_req(config.build_req(ROUTE)))?.json()?;
+    Ok(tlds.into_iter().map(|tld| tld.name).collect())
+}
+
+/// Implement the "check sweep" subcommand
+pub struct DomainSweepCheckCommand {}
+
+impl GandiSubCommandHandler for DomainSweepCheckCommand {
+    const PATH: &'static [&'static str] = &["check", "sweep"];
+    const READ_ONLY: bool = true;
+    // many requests, see `process`
+    const WATCH: bool = false;
+    type Item = Vec<CheckReport>;
+
+    /// Create the route of the tlds, the domains are checked by `process`
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        SharingSpace::from(params).build_req(config.build_req(ROUTE))
+    }
+
+    /// Check the label on every tlds concurrently, and report their
+    /// cheapest registration
+    fn process(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let label = params.value_of("LABEL").unwrap().to_lowercase();
+        let sharing_space = SharingSpace::from(params);
+        let tlds = match params.value_of("TLDS") {
+            Some(path) => read_lines(path)?
+                .into_iter()
+                .map(|tld| tld.trim_start_matches('.').to_string())
+                .collect(),
+            None => fetch_tlds(config, &sharing_space)?,
+        };
+        let fqdns: Vec<String> = tlds
+            .iter()
+            .map(|tld| format!("{}.{}", label, tld))
+            .collect();
+        // checked by the clap validator
+        let jobs = parse_jobs(params.value_of("JOBS").unwrap()).unwrap();
+        let mut reports = check_all(config, &fqdns, &sharing_space, Process::Create, jobs);
+        if let Some(sort_by) = params.value_of("SORT_BY") {
+            sort_reports(&mut reports, sort_by);
+        }
+        let result = failures(&reports);
+        Self::output(reports, &HeaderMap::new(), params)?;
+        result
+    }
+
+    /// Display the count of every categories, and the domains by category
+    fn display_human_result(reports: Self::Item) {
+        for availability in Availability::ALL.iter() {
+            let count = reports
+                .iter()
+                .filter(|report| report.availability() == *availability)
+                .count();
+            print_info(availability.as_str(), count.to_string().as_str());
+        }
+        print_empty_line();
+        let rows: Vec<Vec<String>> = Availability::ALL
+            .iter()
+            .flat_map(|availability| {
+                reports
+                    .iter()
+                    .filter(move |report| report.availability() == *availability)
+            })
+            .map(|report| {
+                let phase = match (
+                    &report.phase,
+                    &report.phase_starts_at,
+                    &report.phase_ends_at,
+                ) {
+                    (Some(phase), Some(starts_at), Some(ends_at)) => format!(
+                        "{} from {} to {}",
+                        phase,
+                        format_date(starts_at),
+                        format_date(ends_at)
+                    ),
+                    (Some(phase), _, _) => phase.clone(),
+                    _ => String::new(),
+                };
+                vec![
+                    report.fqdn.clone(),
+                    report.availability().as_str().to_string(),
+                    status_label(report),
+                    price_label(report),
+                    report.currency.clone().unwrap_or_default(),
+                    report.period.clone().unwrap_or_default(),
+                    phase,
+                ]
+            })
+            .collect();
+        print_table(
+            &[
+                "FQDN", "CATEGORY", "STATUS", "PRICE", "CURRENCY", "PERIOD", "PHASE",
+            ],
+            &rows,
+        );
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Check the availability and the registration price of a label on every tlds")
+            .arg(
+                Arg::with_name("LABEL")
+                    .index(1)
+                    .required(true)
+                    .validator(|label| check_label(&label))
+                    .help("Label to check, without tld, e.g. example"),
+            )
+            .arg(
+                Arg::with_name("TLDS")
+                    .long("tlds")
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .help("File of tlds, one per line, - for stdin. The Gandi catalogue is used by default"),
+            );
+        add_bulk_options(subcommand)
+    }
+}
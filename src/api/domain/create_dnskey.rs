@@ -0,0 +1,85 @@
+//! [Create a DNSSEC key](https://api.gandi.net/docs/domains/#post-v5-domain-domains-domain-dnskeys) route binding
+use clap::{App, ArgMatches, SubCommand};
+use reqwest::{Method, RequestBuilder};
+use serde::Serialize;
+
+use super::super::super::args::dnskey::{
+    add_subcommand_options as add_dnskey_options, DnsKeyFields,
+};
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::config::Configuration;
+use super::super::super::display::add_subcommand_options;
+use super::super::super::errors::{GandiError, GandiResult};
+use super::super::operation::{print_operation, Operation};
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/dnskeys"
+    };
+}
+
+/// Body of the creation
+#[derive(Debug, Serialize)]
+struct DnsKeyCreation {
+    algorithm: u8,
+    flags: u16,
+    public_key: String,
+}
+
+/// Implement the "create dnskey" subcommand
+pub struct DomainDnsKeyCreateCommand {}
+
+impl GandiSubCommandHandler for DomainDnsKeyCreateCommand {
+    const PATH: &'static [&'static str] = &["create", "dnskey"];
+    const READ_ONLY: bool = false;
+    type Item = Operation;
+
+    /// Check the owner of the record is the domain
+    fn prepare(_: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let key = DnsKeyFields::from(params);
+        match key.owner {
+            Some(owner)
+                if owner.ends_with('.')
+                    && !owner
+                        .trim_end_matches('.')
+                        .eq_ignore_ascii_case(fqdn.trim_end_matches('.')) =>
+            {
+                Err(GandiError::ClapError(format!(
+                    "the record of {} cannot be added to {}",
+                    owner, fqdn
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let key = DnsKeyFields::from(params);
+        let body = DnsKeyCreation {
+            algorithm: key.algorithm,
+            flags: key.flags,
+            public_key: key.public_key,
+        };
+        config
+            .build_req_with_method(Method::POST, format!(ROUTE!(), fqdn).as_str())
+            .json(&body)
+    }
+
+    /// Display the operation
+    fn display_human_result(item: Self::Item) {
+        print_operation(&item);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Publish a DNSSEC key of the domain at the registry");
+        let subcommand = add_fqdn_options(subcommand);
+        let subcommand = add_dnskey_options(subcommand);
+        add_subcommand_options(subcommand)
+    }
+}
@@ -0,0 +1,61 @@
+//! [Create a glue record](https://api.gandi.net/docs/domains/#post-v5-domain-domains-domain-hosts) route binding
+use clap::{App, ArgMatches, SubCommand};
+use reqwest::{Method, RequestBuilder};
+use serde::Serialize;
+
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::args::glue_record::{
+    add_subcommand_options as add_glue_record_options, glue_record_ips,
+};
+use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::config::Configuration;
+use super::super::super::display::add_subcommand_options;
+use super::super::operation::{print_operation, Operation};
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/hosts"
+    };
+}
+
+/// Body of the creation
+#[derive(Debug, Serialize)]
+struct GlueRecordCreation {
+    name: String,
+    ips: Vec<String>,
+}
+
+/// Implement the "create glue-record" subcommand
+pub struct DomainGlueRecordCreateCommand {}
+
+impl GandiSubCommandHandler for DomainGlueRecordCreateCommand {
+    const PATH: &'static [&'static str] = &["create", "glue-record"];
+    const READ_ONLY: bool = false;
+    type Item = Operation;
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let body = GlueRecordCreation {
+            name: params.value_of("NAME").unwrap().to_string(),
+            ips: glue_record_ips(params),
+        };
+        config
+            .build_req_with_method(Method::POST, format!(ROUTE!(), fqdn).as_str())
+            .json(&body)
+    }
+
+    /// Display the operation
+    fn display_human_result(item: Self::Item) {
+        print_operation(&item);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Create a host of the domain, to use it as nameserver");
+        let subcommand = add_fqdn_options(subcommand);
+        let subcommand = add_glue_record_options(subcommand, true);
+        add_subcommand_options(subcommand)
+    }
+}
@@ -0,0 +1,68 @@
+//! [Delete a DNSSEC key](https://api.gandi.net/docs/domains/#delete-v5-domain-domains-domain-dnskeys-id) route binding
+use clap::{App, Arg, ArgMatches, SubCommand};
+use reqwest::{Method, RequestBuilder};
+
+use super::super::super::args::confirm::{add_subcommand_options as add_confirm_options, confirm};
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::config::Configuration;
+use super::super::super::display::add_subcommand_options;
+use super::super::super::errors::GandiResult;
+use super::super::operation::{print_operation, Operation};
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/dnskeys/{}"
+    };
+}
+
+/// Implement the "delete dnskey" subcommand
+pub struct DomainDnsKeyDeleteCommand {}
+
+impl GandiSubCommandHandler for DomainDnsKeyDeleteCommand {
+    const PATH: &'static [&'static str] = &["delete", "dnskey"];
+    const READ_ONLY: bool = false;
+    type Item = Operation;
+
+    /// Ask for a confirmation, the domain does not resolve anymore if the
+    /// zone is still signed with the key
+    fn prepare(_: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let id = params.value_of("ID").unwrap();
+        confirm(
+            params,
+            format!(
+                "Delete the key {} of {}? The zone must not be signed with it anymore.",
+                id, fqdn
+            )
+            .as_str(),
+        )
+    }
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let id = params.value_of("ID").unwrap();
+        config.build_req_with_method(Method::DELETE, format!(ROUTE!(), fqdn, id).as_str())
+    }
+
+    /// Display the operation
+    fn display_human_result(item: Self::Item) {
+        print_operation(&item);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Remove a DNSSEC key of the domain from the registry")
+            .arg(
+                Arg::with_name("ID")
+                    .index(2)
+                    .required(true)
+                    .help("Identifier of the key, see show dnskeys"),
+            );
+        let subcommand = add_fqdn_options(subcommand);
+        let subcommand = add_confirm_options(subcommand);
+        add_subcommand_options(subcommand)
+    }
+}
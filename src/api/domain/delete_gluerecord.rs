@@ -0,0 +1,67 @@
+//! [Delete a glue record](https://api.gandi.net/docs/domains/#delete-v5-domain-domains-domain-hosts-name) route binding
+use clap::{App, ArgMatches, SubCommand};
+use reqwest::{Method, RequestBuilder};
+
+use super::super::super::args::confirm::{add_subcommand_options as add_confirm_options, confirm};
+use super::super::super::args::fqdn::{add_fqdn_options, normalize_hostname};
+use super::super::super::args::glue_record::add_subcommand_options as add_glue_record_options;
+use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::config::Configuration;
+use super::super::super::display::{add_subcommand_options, print_warning};
+use super::super::super::errors::GandiResult;
+use super::super::operation::{print_operation, Operation};
+use super::update_nameservers::fetch_nameservers;
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/hosts/{}"
+    };
+}
+
+/// Implement the "delete glue-record" subcommand
+pub struct DomainGlueRecordDeleteCommand {}
+
+impl GandiSubCommandHandler for DomainGlueRecordDeleteCommand {
+    const PATH: &'static [&'static str] = &["delete", "glue-record"];
+    const READ_ONLY: bool = false;
+    type Item = Operation;
+
+    /// Warn if the host is still a nameserver of the domain, and ask for a confirmation
+    fn prepare(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let host = normalize_hostname(&format!("{}.{}", params.value_of("NAME").unwrap(), fqdn));
+        let nameservers = fetch_nameservers(config, fqdn)?;
+        if nameservers.iter().any(|ns| normalize_hostname(ns) == host) {
+            print_warning(
+                format!(
+                    "{} is still a nameserver of {}, it will not be resolved anymore",
+                    host, fqdn
+                )
+                .as_str(),
+            );
+            confirm(params, format!("Delete {}?", host).as_str())?;
+        }
+        Ok(())
+    }
+
+    /// Create the route, the glue records are named in lowercase
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let name = normalize_hostname(params.value_of("NAME").unwrap());
+        config.build_req_with_method(Method::DELETE, format!(ROUTE!(), fqdn, name).as_str())
+    }
+
+    /// Display the operation
+    fn display_human_result(item: Self::Item) {
+        print_operation(&item);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name()).about("Delete a host of the domain");
+        let subcommand = add_fqdn_options(subcommand);
+        let subcommand = add_glue_record_options(subcommand, false);
+        let subcommand = add_confirm_options(subcommand);
+        add_subcommand_options(subcommand)
+    }
+}
@@ -3,145 +3,238 @@ use std::vec::Vec;
 
 use chrono::{DateTime, Utc};
 use clap::{App, ArgMatches, SubCommand};
-use reqwest::header::HeaderMap;
+use reqwest::header::{HeaderMap, HeaderValue};
 use reqwest::RequestBuilder;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use super::super::super::args::domain_filter::{
+    add_subcommand_options as add_domain_filter_options, DomainFilter,
+};
 use super::super::super::args::pagination::{
     add_subcommand_options as add_pagination_options, Pagination,
 };
 use super::super::super::args::sharing_id::{
     add_subcommand_options as add_sharing_id_options, SharingSpace,
 };
-use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::command_handler::{send, GandiSubCommandHandler};
 use super::super::super::config::Configuration;
-use super::super::super::display::{add_subcommand_options, print_flag, print_info, print_tags};
+use super::super::super::display::{
+    add_subcommand_options, print_deadline, print_empty_line, print_flag, print_info, print_tags,
+};
 use super::super::super::errors::GandiResult;
-use super::super::super::formatter::date_formatter_z;
-use super::super::super::formatter::optional_date_formatter_z;
+use super::super::super::formatter::date_formatter;
+use super::super::super::formatter::string_enum;
+use super::super::super::formatter::Extra;
 
 pub const ROUTE: &str = "/v5/domain/domains";
 
+string_enum! {
+    /// Kind of nameservers of a domain
+    pub enum NameServerType {
+        Abc => "abc",
+        LiveDns => "livedns",
+        Other => "other",
+    }
+}
+
 /// Name Server Information
-#[derive(Debug, Serialize, Deserialize)]
-struct NameServer {
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NameServer {
     /// Label of the nameserver (abc, livedns or other)
-    current: String,
+    pub current: NameServerType,
     /// In the doc, but always null
     #[serde(skip_serializing_if = "Option::is_none")]
-    hosts: Option<Vec<String>>,
+    pub hosts: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Domain's life cycle dates.
-#[derive(Debug, Serialize, Deserialize)]
-struct Dates {
-    #[serde(with = "date_formatter_z")]
-    registry_created_at: DateTime<Utc>,
-    #[serde(with = "date_formatter_z")]
-    updated_at: DateTime<Utc>,
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Dates {
+    #[serde(with = "date_formatter")]
+    #[schemars(schema_with = "date_formatter::schema")]
+    pub registry_created_at: DateTime<Utc>,
+    #[serde(with = "date_formatter")]
+    #[schemars(schema_with = "date_formatter::schema")]
+    pub updated_at: DateTime<Utc>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    authinfo_expires_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub authinfo_expires_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    created_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub created_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    deletes_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub deletes_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    hold_begins_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub hold_begins_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    hold_ends_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub hold_ends_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    pending_delete_ends_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub pending_delete_ends_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    registry_ends_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub registry_ends_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    renew_begins_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub renew_begins_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    restore_ends_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub restore_ends_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Domain Information Format, returned by the API
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Domain {
     /// the id of the domain
-    id: String,
+    pub id: String,
 
     /// UNDOCUMENTED
-    orga_owner: String,
+    pub orga_owner: String,
     /// UNDOCUMENTED
-    owner: String,
+    pub owner: String,
 
     /// the sharing id of the owner (an organization id)
-    sharing_id: Option<String>,
+    pub sharing_id: Option<String>,
 
     /// Fully qualified domain name, written in its native alphabet (IDN)
-    fqdn: String,
+    pub fqdn: String,
     /// Fully qualified domain name, written in unicode
-    fqdn_unicode: String,
+    pub fqdn_unicode: String,
     /// flag to renew automatically the domain name before it expires
-    autorenew: bool,
+    pub autorenew: bool,
     /// the tld of the domain
-    tld: String,
+    pub tld: String,
 
     /// tags
-    tags: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
 
     /// Domain's life cycle dates
-    dates: Dates,
+    pub dates: Dates,
 
     /// flag to renew automatically the domain name before it expires
-    nameserver: NameServer,
+    pub nameserver: NameServer,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Number of domains fetched per request by `DomainListCommand::fetch_all`
+const FETCH_ALL_PER_PAGE: usize = 100;
+
+/// Build the request of one page of the domain list
+fn build_list_req(
+    config: &Configuration,
+    pagination: &Pagination,
+    filter: &DomainFilter,
+    sharing_space: &SharingSpace,
+) -> RequestBuilder {
+    let req = config.build_req(ROUTE);
+    let req = pagination.build_req(req);
+    let req = filter.build_req(req);
+    sharing_space.build_req(req)
 }
 
 pub struct DomainListCommand {}
 
+impl DomainListCommand {
+    /// Fetch the domains of every pages of the list that match the filter,
+    /// including its tag.
+    pub fn fetch_all(
+        config: &Configuration,
+        filter: &DomainFilter,
+        sharing_space: &SharingSpace,
+    ) -> GandiResult<Vec<Domain>> {
+        let mut domains = Vec::new();
+        let mut page = 1;
+        loop {
+            let pagination = Pagination {
+                page: page.to_string(),
+                per_page: FETCH_ALL_PER_PAGE.to_string(),
+            };
+            let req = build_list_req(config, &pagination, filter, sharing_space);
+            let mut items: Vec<Domain> = send(req)?.json()?;
+            let count = items.len();
+            domains.append(&mut items);
+            if count < FETCH_ALL_PER_PAGE {
+                break;
+            }
+            page += 1;
+        }
+        Ok(domains
+            .into_iter()
+            .filter(|domain| filter.matches(domain))
+            .collect())
+    }
+}
+
 impl GandiSubCommandHandler for DomainListCommand {
-    const COMMAND_GROUP: &'static str = "list";
-    const COMMAND: &'static str = "domains";
+    const PATH: &'static [&'static str] = &["list", "domains"];
+    const READ_ONLY: bool = true;
     type Item = Vec<Domain>;
 
     /// Create the route
     fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
         let pagination = Pagination::from(params);
         let sharing_space = SharingSpace::from(params);
-        let req = config.build_req(ROUTE);
-        let req = pagination.build_req(req);
-        sharing_space.build_req(req)
+        let filter = DomainFilter::from(params);
+        build_list_req(config, &pagination, &filter, &sharing_space)
+    }
+
+    /// Fetch every pages when filtering on a tag, the route cannot filter them
+    fn query(config: &Configuration, params: &ArgMatches) -> GandiResult<(Self::Item, HeaderMap)> {
+        let filter = DomainFilter::from(params);
+        if filter.tag.is_none() {
+            let mut resp = send(Self::build_req(config, params))?;
+            let items: Self::Item = resp.json()?;
+            return Ok((items, resp.headers().clone()));
+        }
+        let items = Self::fetch_all(config, &filter, &SharingSpace::from(params))?;
+        let mut headers = HeaderMap::new();
+        headers.insert("Total-Count", HeaderValue::from(items.len()));
+        Ok((items, headers))
     }
 
     /// Override it to display extra informations from the response header
@@ -150,7 +243,7 @@ impl GandiSubCommandHandler for DomainListCommand {
             .get("Total-Count")
             .map(|hdr| hdr.to_str().unwrap())
             .unwrap_or("MISSING");
-        println!("");
+        print_empty_line();
         print_info("Total Count of domains:", total_count);
         Ok(())
     }
@@ -158,7 +251,7 @@ impl GandiSubCommandHandler for DomainListCommand {
     /// Display the domain important data
     fn display_human_result(items: Self::Item) {
         for domain in items {
-            println!("");
+            print_empty_line();
             print_info("fqdn", domain.fqdn_unicode.as_str());
             print_info("id", domain.id.as_str());
             print_info("organization", domain.orga_owner.as_str());
@@ -169,14 +262,16 @@ impl GandiSubCommandHandler for DomainListCommand {
             // print_info("tld", domain.tld.as_str());
             // print_info("nameserver", domain.nameserver.current.as_str());
             print_flag("autorenew", domain.autorenew);
+            print_deadline("expires at", domain.dates.registry_ends_at.as_ref());
             print_tags(&domain.tags);
         }
     }
 
     /// Create the clap subcommand with its arguments.
     fn subcommand<'a, 'b>() -> App<'a, 'b> {
-        let subcommand = SubCommand::with_name(Self::COMMAND);
+        let subcommand = SubCommand::with_name(Self::name());
         let subcommand = add_pagination_options(subcommand);
+        let subcommand = add_domain_filter_options(subcommand);
         let subcommand = add_sharing_id_options(subcommand);
         add_subcommand_options(subcommand)
     }
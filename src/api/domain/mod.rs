@@ -1,14 +1,74 @@
 /// [domains check](https://api.gandi.net/docs/domains/#get-v5-domain-check) route binding
 pub mod check;
 
+/// bulk [domains check](https://api.gandi.net/docs/domains/#get-v5-domain-check) binding
+pub mod check_domains;
+
+/// label [check on every tlds](https://api.gandi.net/docs/domains/#get-v5-domain-tlds) binding
+pub mod check_sweep;
+
+/// [domain create](https://api.gandi.net/docs/domains/#post-v5-domain-domains) route binding
+pub mod create;
+
+/// [domain's DNSSEC key create](https://api.gandi.net/docs/domains/#post-v5-domain-domains-domain-dnskeys) route binding
+pub mod create_dnskey;
+
+/// [domain's glue-record create](https://api.gandi.net/docs/domains/#post-v5-domain-domains-domain-hosts) route binding
+pub mod create_gluerecord;
+
+/// [domain's DNSSEC key delete](https://api.gandi.net/docs/domains/#delete-v5-domain-domains-domain-dnskeys-id) route binding
+pub mod delete_dnskey;
+
+/// [domain's glue-record delete](https://api.gandi.net/docs/domains/#delete-v5-domain-domains-domain-hosts-name) route binding
+pub mod delete_gluerecord;
+
 /// [domains list](https://api.gandi.net/docs/domains/#get-v5-domain-domains) route binding
 pub mod list;
 
+/// [domain renew](https://api.gandi.net/docs/domains/#post-v5-domain-domains-domain-renew) route binding
+pub mod renew;
+
 /// [domain show](https://api.gandi.net/docs/domains/#get-v5-domain-domains-domain) route binding
 pub mod show;
 
+/// [domain's authinfo show](https://api.gandi.net/docs/domains/#v5-domain-domains-domain) route binding
+pub mod show_authinfo;
+
 /// [domain's contacts show](https://api.gandi.net/docs/domains/#get-v5-domain-domains-domain-contacts) route binding
 pub mod show_contacts;
 
+/// [domain's DNSSEC keys show](https://api.gandi.net/docs/domains/#get-v5-domain-domains-domain-dnskeys) route binding
+pub mod show_dnskeys;
+
 /// [domain's glue-record show](https://api.gandi.net/docs/domains/#get-v5-domain-domains-domain-hosts) route binding
 pub mod show_gluerecords;
+
+/// [domain's transfer lock show](https://api.gandi.net/docs/domains/#v5-domain-domains-domain) route binding
+pub mod show_transferlock;
+
+/// [domain's tags update](https://api.gandi.net/docs/domains/#put-v5-domain-domains-domain-tags) route binding
+pub mod tags;
+
+/// [domain transfer](https://api.gandi.net/docs/domains/#post-v5-domain-transferin) route binding
+pub mod transferin;
+
+/// [domain transfer status](https://api.gandi.net/docs/domains/#get-v5-domain-transferin-domain) route binding
+pub mod transferin_status;
+
+/// [domain's authinfo reset](https://api.gandi.net/docs/domains/#post-v5-domain-domains-domain-authinfo) route binding
+pub mod update_authinfo;
+
+/// [domain's autorenew update](https://api.gandi.net/docs/domains/#patch-v5-domain-domains-domain-autorenew) route binding
+pub mod update_autorenew;
+
+/// [domain's contacts update](https://api.gandi.net/docs/domains/#patch-v5-domain-domains-domain-contacts) route binding
+pub mod update_contacts;
+
+/// [domain's glue-record update](https://api.gandi.net/docs/domains/#put-v5-domain-domains-domain-hosts-name) route binding
+pub mod update_gluerecord;
+
+/// [domain's nameservers update](https://api.gandi.net/docs/domains/#put-v5-domain-domains-domain-nameservers) route binding
+pub mod update_nameservers;
+
+/// [domain's transfer lock update](https://api.gandi.net/docs/domains/#patch-v5-domain-domains-domain-status) route binding
+pub mod update_transferlock;
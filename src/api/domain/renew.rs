@@ -0,0 +1,98 @@
+//! [Renew a domain](https://api.gandi.net/docs/domains/#post-v5-domain-domains-domain-renew) route binding
+use chrono::{DateTime, Datelike, Duration, Utc};
+use clap::{App, ArgMatches, SubCommand};
+use reqwest::{Method, RequestBuilder};
+use serde::Serialize;
+
+use super::super::super::args::confirm::{add_subcommand_options as add_confirm_options, confirm};
+use super::super::super::args::duration::{
+    add_subcommand_options as add_duration_options, DomainDuration,
+};
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::args::sharing_id::{
+    add_subcommand_options as add_sharing_id_options, SharingSpace,
+};
+use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::config::Configuration;
+use super::super::super::display::{
+    add_subcommand_options, format_date, print_notice, print_notice_info,
+};
+use super::super::super::errors::GandiResult;
+use super::super::operation::{print_operation, Operation};
+use super::check::{quote, Process};
+use super::show::DomainShowCommand;
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/renew"
+    };
+}
+
+/// Body of the renewal
+#[derive(Debug, Serialize)]
+struct DomainRenewal {
+    /// number of years
+    duration: usize,
+}
+
+/// Add years to the date, the 29th of February becomes the 1st of March.
+fn add_years(date: &DateTime<Utc>, years: usize) -> DateTime<Utc> {
+    let year = date.year() + years as i32;
+    date.with_year(year)
+        .unwrap_or_else(|| (*date + Duration::days(1)).with_year(year).unwrap())
+}
+
+/// Implement the "renew domain" subcommand
+pub struct DomainRenewCommand {}
+
+impl GandiSubCommandHandler for DomainRenewCommand {
+    const PATH: &'static [&'static str] = &["renew", "domain"];
+    const READ_ONLY: bool = false;
+    type Item = Operation;
+
+    /// Display the price and the registry end date, and ask for a confirmation
+    fn prepare(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let duration = DomainDuration::from(params);
+        let sharing_space = SharingSpace::from(params);
+        let quote = quote(config, fqdn, &sharing_space, Process::Renew, duration.years)?;
+        let domain = DomainShowCommand::fetch(config, &DomainShowCommand::params_from(&[fqdn])?)?;
+        print_notice(quote.to_string().as_str());
+        if let Some(ends_at) = domain.dates.registry_ends_at.as_ref() {
+            print_notice_info("registry ends at", format_date(ends_at).as_str());
+            print_notice_info(
+                "registry ends at, once renewed",
+                format_date(&add_years(ends_at, duration.years)).as_str(),
+            );
+        }
+        confirm(params, format!("Renew {}?", fqdn).as_str())
+    }
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let body = DomainRenewal {
+            duration: DomainDuration::from(params).years,
+        };
+        let req = config
+            .build_req_with_method(Method::POST, format!(ROUTE!(), fqdn).as_str())
+            .json(&body);
+        SharingSpace::from(params).build_req(req)
+    }
+
+    /// Display the operation
+    fn display_human_result(item: Self::Item) {
+        print_operation(&item);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Renew a domain, the price is displayed before");
+        let subcommand = add_fqdn_options(subcommand);
+        let subcommand = add_duration_options(subcommand);
+        let subcommand = add_sharing_id_options(subcommand);
+        let subcommand = add_confirm_options(subcommand);
+        add_subcommand_options(subcommand)
+    }
+}
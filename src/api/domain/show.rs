@@ -4,16 +4,18 @@ use std::vec::Vec;
 use chrono::{DateTime, Utc};
 use clap::{App, ArgMatches, SubCommand};
 use reqwest::RequestBuilder;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::super::super::args::fqdn::add_fqdn_options;
 use super::super::super::command_handler::GandiSubCommandHandler;
 use super::super::super::config::Configuration;
 use super::super::super::display::{
-    add_subcommand_options, print_flag, print_info, print_list, print_tags,
+    add_subcommand_options, print_date, print_deadline, print_flag, print_info, print_list,
+    print_tags,
 };
-use super::super::super::formatter::date_formatter_z;
-use super::super::super::formatter::optional_date_formatter_z;
+use super::super::super::formatter::date_formatter;
+use super::super::super::formatter::Extra;
 use super::show_contacts::{print_contacts, Contacts, SharingSpace};
 
 macro_rules! ROUTE {
@@ -23,125 +25,162 @@ macro_rules! ROUTE {
 }
 
 /// Autorenew Informations
-#[derive(Debug, Serialize, Deserialize)]
-struct Autorenew {
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Autorenew {
     // what is this ?
-    href: String,
+    pub href: String,
     // dates ⁠array[ datetime ]
     #[serde(skip_serializing_if = "Option::is_none")]
-    date: Option<Vec<String>>,
+    pub date: Option<Vec<String>>,
     // not explained - should have a duration unit too ?
-    duration: usize,
+    pub duration: usize,
     // use it to disable the autorenew
-    enabled: bool,
+    pub enabled: bool,
     /// sharing_id that pay the renew
-    org_id: Option<String>,
+    pub org_id: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Domain's life cycle dates.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Dates {
-    #[serde(with = "date_formatter_z")]
-    registry_created_at: DateTime<Utc>,
-    #[serde(with = "date_formatter_z")]
-    updated_at: DateTime<Utc>,
+    #[serde(with = "date_formatter")]
+    #[schemars(schema_with = "date_formatter::schema")]
+    pub registry_created_at: DateTime<Utc>,
+    #[serde(with = "date_formatter")]
+    #[schemars(schema_with = "date_formatter::schema")]
+    pub updated_at: DateTime<Utc>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    authinfo_expires_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub authinfo_expires_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    created_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub created_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    deletes_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub deletes_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    hold_begins_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub hold_begins_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    hold_ends_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub hold_ends_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    pending_delete_ends_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub pending_delete_ends_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    registry_ends_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub registry_ends_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    renew_begins_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub renew_begins_at: Option<DateTime<Utc>>,
     #[serde(
         default,
-        with = "optional_date_formatter_z",
+        with = "date_formatter::optional",
         skip_serializing_if = "Option::is_none"
     )]
-    restore_ends_at: Option<DateTime<Utc>>,
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub restore_ends_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Domain Information Format, returned by the API
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Domain {
     /// the id of the domain
     // optional ?
-    id: String,
+    pub id: String,
     /// Fully qualified domain name, written in its native alphabet (IDN)
-    fqdn: String,
+    pub fqdn: String,
     /// Fully qualified domain name, written in unicode
-    fqdn_unicode: String,
+    pub fqdn_unicode: String,
     /// the tld of the domain
-    tld: String,
+    pub tld: String,
     /// information assiociated to the tld of the domain, about lock registry support
-    can_tld_lock: bool,
+    pub can_tld_lock: bool,
     /// the authinfo code used to transfer out the domain
-    authinfo: String,
+    pub authinfo: String,
     /// fqdn of name servers
     #[serde(skip_serializing_if = "Option::is_none")]
-    nameservers: Option<Vec<String>>,
+    pub nameservers: Option<Vec<String>>,
     /// List of Gandi services attached to this domain
     #[serde(skip_serializing_if = "Option::is_none")]
-    services: Option<Vec<String>>,
+    pub services: Option<Vec<String>>,
     /// list of tags
     #[serde(skip_serializing_if = "Option::is_none")]
-    tags: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
+    /// status of the domain, e.g. `clientTransferProhibited`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Vec<String>>,
     /// the organization that own the domain
-    sharing_space: SharingSpace,
+    pub sharing_space: SharingSpace,
     // sharing_id: String optional, not sent, but we have the sharing_space here
     /// autorenew informations
-    autorenew: Autorenew,
+    pub autorenew: Autorenew,
     /// Domain's life cycle dates
-    dates: Dates,
-    contacts: Contacts,
+    pub dates: Dates,
+    pub contacts: Contacts,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Display the life cycle dates, the deadlines are highlighted
+fn print_dates(dates: &Dates) {
+    print_date("registry created at", Some(&dates.registry_created_at));
+    print_date("updated at", Some(&dates.updated_at));
+    print_deadline("registry ends at", dates.registry_ends_at.as_ref());
+    print_date("renew begins at", dates.renew_begins_at.as_ref());
+    print_date("hold begins at", dates.hold_begins_at.as_ref());
+    print_deadline("hold ends at", dates.hold_ends_at.as_ref());
+    print_deadline("restore ends at", dates.restore_ends_at.as_ref());
+    print_deadline(
+        "pending delete ends at",
+        dates.pending_delete_ends_at.as_ref(),
+    );
+    print_deadline("deletes at", dates.deletes_at.as_ref());
+    print_date("authinfo expires at", dates.authinfo_expires_at.as_ref());
 }
 
 /// Implement the "show domain" subcommand
 pub struct DomainShowCommand {}
 
 impl GandiSubCommandHandler for DomainShowCommand {
-    const COMMAND_GROUP: &'static str = "show";
-    const COMMAND: &'static str = "domain";
+    const PATH: &'static [&'static str] = &["show", "domain"];
+    const READ_ONLY: bool = true;
 
     type Item = Domain;
 
@@ -158,13 +197,15 @@ impl GandiSubCommandHandler for DomainShowCommand {
         print_flag("autorenew", domain.autorenew.enabled);
         print_list("nameservers", &domain.nameservers);
         print_list("services", &domain.services);
+        print_list("status", &domain.status);
         print_contacts(&domain.contacts, Some(&domain.sharing_space));
         print_tags(&domain.tags);
+        print_dates(&domain.dates);
     }
 
     /// Create the clap subcommand with its arguments.
     fn subcommand<'a, 'b>() -> App<'a, 'b> {
-        let subcommand = SubCommand::with_name(Self::COMMAND);
+        let subcommand = SubCommand::with_name(Self::name());
         let subcommand = add_fqdn_options(subcommand);
         add_subcommand_options(subcommand)
     }
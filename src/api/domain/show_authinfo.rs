@@ -0,0 +1,106 @@
+//! [Show the domain authinfo](https://api.gandi.net/docs/domains/#v5-domain-domains-domain) route binding
+use chrono::{DateTime, Utc};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use reqwest::header::HeaderMap;
+use reqwest::RequestBuilder;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::config::Configuration;
+use super::super::super::display::{add_subcommand_options, print_date, print_info, Format};
+use super::super::super::errors::GandiResult;
+use super::super::super::formatter::date_formatter;
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}"
+    };
+}
+
+/// Displayed instead of the authinfo, its length is not revealed
+const MASK: &str = "********";
+
+/// Dates of the authinfo, the other dates of the domain are ignored
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AuthinfoDates {
+    #[serde(
+        default,
+        with = "date_formatter::optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(schema_with = "date_formatter::optional::schema")]
+    pub authinfo_expires_at: Option<DateTime<Utc>>,
+}
+
+/// Authinfo of a domain, read from the domain information
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Authinfo {
+    pub fqdn: String,
+    /// the authinfo code used to transfer out the domain
+    pub authinfo: String,
+    pub dates: AuthinfoDates,
+}
+
+/// Display the authinfo, masked in the human format unless `--reveal` is set.
+pub fn output_authinfo<H>(mut item: Authinfo, params: &ArgMatches) -> GandiResult<()>
+where
+    H: GandiSubCommandHandler<Item = Authinfo>,
+{
+    if Format::from(params) == Format::HUMAN && !params.is_present("REVEAL") {
+        item.authinfo = MASK.to_string();
+    }
+    H::output(item, &HeaderMap::new(), params)
+}
+
+/// Add the `--reveal` option to the clap subcommand
+pub fn add_reveal_option<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("REVEAL")
+            .long("reveal")
+            .help("Display the authinfo, it is masked in the human format"),
+    )
+}
+
+/// Implement the "show authinfo" subcommand
+pub struct DomainAuthinfoShowCommand {}
+
+impl GandiSubCommandHandler for DomainAuthinfoShowCommand {
+    const PATH: &'static [&'static str] = &["show", "authinfo"];
+    const READ_ONLY: bool = true;
+    // a secret, not to be left on a screen
+    const WATCH: bool = false;
+    type Item = Authinfo;
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        config.build_req(format!(ROUTE!(), fqdn).as_str())
+    }
+
+    /// Fetch the authinfo, and mask it
+    fn process(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let item = Self::fetch(config, params)?;
+        output_authinfo::<Self>(item, params)
+    }
+
+    /// Display the authinfo
+    fn display_human_result(item: Self::Item) {
+        print_info("fqdn", item.fqdn.as_str());
+        print_info("authinfo", item.authinfo.as_str());
+        print_date(
+            "authinfo expires at",
+            item.dates.authinfo_expires_at.as_ref(),
+        );
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Show the authinfo code used to transfer the domain to another registrar");
+        let subcommand = add_fqdn_options(subcommand);
+        let subcommand = add_reveal_option(subcommand);
+        add_subcommand_options(subcommand)
+    }
+}
@@ -1,14 +1,20 @@
 //! [Show domain information](https://api.gandi.net/docs/domains/#v5-domain-domains-domain) route binding
 use std::collections::HashMap;
+use std::fmt::{self, Display};
 
 use clap::{App, ArgMatches, SubCommand};
 use reqwest::RequestBuilder;
-use serde::{Deserialize, Serialize};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use super::super::super::args::fqdn::add_fqdn_options;
 use super::super::super::command_handler::GandiSubCommandHandler;
 use super::super::super::config::Configuration;
 use super::super::super::display::{add_subcommand_options, print_info};
+use super::super::super::formatter::string_enum;
+use super::super::super::formatter::Extra;
 
 macro_rules! ROUTE {
     () => {
@@ -17,112 +23,229 @@ macro_rules! ROUTE {
 }
 
 /// Organization information
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SharingSpace {
     /// id that pay the renew
-    id: String,
+    pub id: String,
     /// sharing_id that pay the renew
-    name: String,
+    pub name: String,
     /// reseller flag organization
     #[serde(skip_serializing_if = "Option::is_none")]
-    reseller: Option<bool>,
+    pub reseller: Option<bool>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Type of a contact, exposed as an integer by the API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContactType {
+    Person,
+    Company,
+    Association,
+    PublicBody,
+    /// Value not known by this version of the CLI
+    Unknown(u64),
+}
+
+impl ContactType {
+    /// The value, as exposed by the API
+    pub fn as_u64(self) -> u64 {
+        match self {
+            ContactType::Person => 0,
+            ContactType::Company => 1,
+            ContactType::Association => 2,
+            ContactType::PublicBody => 3,
+            ContactType::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<u64> for ContactType {
+    fn from(value: u64) -> Self {
+        match value {
+            0 => ContactType::Person,
+            1 => ContactType::Company,
+            2 => ContactType::Association,
+            3 => ContactType::PublicBody,
+            _ => ContactType::Unknown(value),
+        }
+    }
+}
+
+/// Name of the type in the human format
+impl Display for ContactType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ContactType::Person => write!(f, "person"),
+            ContactType::Company => write!(f, "company"),
+            ContactType::Association => write!(f, "association"),
+            ContactType::PublicBody => write!(f, "public body"),
+            ContactType::Unknown(value) => write!(f, "unknown type {}", value),
+        }
+    }
+}
+
+impl Serialize for ContactType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.as_u64())
+    }
+}
+
+impl<'de> Deserialize<'de> for ContactType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(ContactType::from)
+    }
+}
+
+impl JsonSchema for ContactType {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "ContactType".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        u64::json_schema(gen)
+    }
+}
+
+string_enum! {
+    /// State of the reachability and of the validation of a contact
+    pub enum ContactCheckStatus {
+        Pending => "pending",
+        Done => "done",
+        Failed => "failed",
+        Deleted => "deleted",
+        None => "none",
+    }
 }
 
 /// Contact information
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Contact {
     /// Will be true when the contact used is the same as the owner.
     /// always none for the owner contact, because it does not make sense.
     #[serde(skip_serializing_if = "Option::is_none")]
-    same_as_owner: Option<bool>,
+    pub same_as_owner: Option<bool>,
 
     /// 0: person, 1: company, 2: association, 3: public body
     // 4: reseller is bad
     #[serde(rename(deserialize = "type", serialize = "type"))]
-    type_: usize,
+    pub type_: ContactType,
 
     /// legal name of the company, association, or public body if the contact type is not 0.
     #[serde(skip_serializing_if = "Option::is_none")]
-    orgname: Option<String>,
-    given: String,
-    family: String,
-    streetaddr: String,
+    pub orgname: Option<String>,
+    pub given: String,
+    pub family: String,
+    pub streetaddr: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    zip: Option<String>,
+    pub zip: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    city: Option<String>,
+    pub city: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    state: Option<String>,
-    country: String,
+    pub state: Option<String>,
+    pub country: String,
 
-    email: String,
+    pub email: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    mail_obfuscated: Option<bool>,
+    pub mail_obfuscated: Option<bool>,
     // why both ?
     /// One of "pending", "done", "failed", "deleted", "none"
     #[serde(skip_serializing_if = "Option::is_none")]
-    reachability: Option<String>,
+    pub reachability: Option<ContactCheckStatus>,
     /// One of "pending", "done", "failed", "deleted", "none"
     #[serde(skip_serializing_if = "Option::is_none")]
-    validation: Option<String>,
+    pub validation: Option<ContactCheckStatus>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    phone: Option<String>,
+    pub phone: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    fax: Option<String>,
+    pub fax: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    mobile: Option<String>,
+    pub mobile: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    data_obfuscated: Option<bool>,
+    pub data_obfuscated: Option<bool>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    extra_parameters: Option<HashMap<String, String>>,
+    pub extra_parameters: Option<HashMap<String, String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    siren: Option<String>,
+    pub siren: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    brand_number: Option<String>,
+    pub brand_number: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    jo_announce_number: Option<String>,
+    pub jo_announce_number: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    jo_announce_page: Option<String>,
+    pub jo_announce_page: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    jo_declaration_date: Option<String>,
+    pub jo_declaration_date: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    jo_publication_date: Option<String>,
+    pub jo_publication_date: Option<String>,
     // One: of: "pending", "done", "failed", "deleted", "none"
 
     // why is there a sharing_id here ?
     #[serde(skip_serializing_if = "Option::is_none")]
-    sharing_id: Option<String>,
+    pub sharing_id: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Contacts {
-    owner: Contact,
-    admin: Contact,
-    tech: Contact,
-    bill: Contact,
+    pub owner: Contact,
+    pub admin: Contact,
+    pub tech: Contact,
+    pub bill: Contact,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-/// Helper to print tags in the human format
-pub fn print_contact(type_: &str, contact: &Contact, sharing_space: Option<&SharingSpace>) {
-    let mut contact = if contact.type_ == 0 {
+impl Contacts {
+    /// The contact of the role, e.g. `admin`
+    pub fn get(&self, role: &str) -> Option<&Contact> {
+        match role {
+            "owner" => Some(&self.owner),
+            "admin" => Some(&self.admin),
+            "tech" => Some(&self.tech),
+            "bill" => Some(&self.bill),
+            _ => None,
+        }
+    }
+}
+
+/// Name, email and type of the contact, in the human format
+pub fn contact_label(contact: &Contact) -> String {
+    if contact.type_ == ContactType::Person {
         format!(
             r#""{} {}" <{}>"#,
             contact.given, contact.family, contact.email
         )
     } else {
         format!(
-            r#""{}" <{}>"#,
+            r#""{}" ({}) <{}>"#,
             contact
                 .orgname
                 .as_ref()
                 .map(|orgname| orgname.as_str())
                 .unwrap_or("NO ORGNAME SET"),
+            contact.type_,
             contact.email
         )
-    };
+    }
+}
+
+/// Helper to print tags in the human format
+pub fn print_contact(type_: &str, contact: &Contact, sharing_space: Option<&SharingSpace>) {
+    let mut contact = contact_label(contact);
     if let Some(sharing) = sharing_space {
         contact = format!("{} ({})", contact, sharing.name);
     }
@@ -147,8 +270,8 @@ pub fn print_contacts(contacts: &Contacts, sharing_space: Option<&SharingSpace>)
 pub struct DomainContactsShowCommand {}
 
 impl GandiSubCommandHandler for DomainContactsShowCommand {
-    const COMMAND_GROUP: &'static str = "show";
-    const COMMAND: &'static str = "contacts";
+    const PATH: &'static [&'static str] = &["show", "contacts"];
+    const READ_ONLY: bool = true;
     type Item = Contacts;
 
     /// Create the route
@@ -164,7 +287,7 @@ impl GandiSubCommandHandler for DomainContactsShowCommand {
 
     /// Create the clap subcommand with its arguments.
     fn subcommand<'a, 'b>() -> App<'a, 'b> {
-        let subcommand = SubCommand::with_name(Self::COMMAND);
+        let subcommand = SubCommand::with_name(Self::name());
         let subcommand = add_fqdn_options(subcommand);
         add_subcommand_options(subcommand)
     }
@@ -0,0 +1,114 @@
+//! [Show the domain DNSSEC keys](https://api.gandi.net/docs/domains/#get-v5-domain-domains-domain-dnskeys) route binding
+use clap::{App, ArgMatches, SubCommand};
+use reqwest::header::HeaderMap;
+use reqwest::RequestBuilder;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::command_handler::{send, GandiSubCommandHandler};
+use super::super::super::config::Configuration;
+use super::super::super::display::{add_subcommand_options, print_empty_line, print_info};
+use super::super::super::dnssec::{
+    algorithm_name, decode_public_key, digest_type_name, ds_digests, key_tag, Ds,
+};
+use super::super::super::errors::GandiResult;
+use super::super::super::formatter::Extra;
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/dnskeys"
+    };
+}
+
+/// DNSKEY published at the registry
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DnsKey {
+    /// identifier of the key, used to delete it
+    pub id: String,
+    /// DNSSEC algorithm number
+    pub algorithm: u8,
+    /// 256 for a ZSK, 257 for a KSK
+    pub flags: u16,
+    /// base64 encoded public key
+    pub public_key: String,
+    /// key tag, as sent by the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_tag: Option<u16>,
+    /// key tag, computed from the key
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub computed_key_tag: Option<u16>,
+    /// DS digests, computed from the key
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub ds_digests: Option<Vec<Ds>>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl DnsKey {
+    /// Compute the key tag and the DS digests of the key of the domain
+    fn with_digests(mut self, fqdn: &str) -> Self {
+        if let Ok(public_key) = decode_public_key(&self.public_key) {
+            self.computed_key_tag = Some(key_tag(self.flags, self.algorithm, &public_key));
+            self.ds_digests = Some(ds_digests(fqdn, self.flags, self.algorithm, &public_key));
+        }
+        self
+    }
+}
+
+/// Implement the "show dnskeys" subcommand
+pub struct DomainDnsKeysShowCommand {}
+
+impl GandiSubCommandHandler for DomainDnsKeysShowCommand {
+    const PATH: &'static [&'static str] = &["show", "dnskeys"];
+    const READ_ONLY: bool = true;
+    type Item = Vec<DnsKey>;
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        config.build_req(format!(ROUTE!(), fqdn).as_str())
+    }
+
+    /// Fetch the keys, and compute their key tag and DS digests
+    fn query(config: &Configuration, params: &ArgMatches) -> GandiResult<(Self::Item, HeaderMap)> {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let mut resp = send(Self::build_req(config, params))?;
+        let keys: Self::Item = resp.json()?;
+        let keys = keys.into_iter().map(|key| key.with_digests(fqdn)).collect();
+        Ok((keys, resp.headers().clone()))
+    }
+
+    /// Display the keys, the DS records are written in the zone file syntax
+    fn display_human_result(keys: Self::Item) {
+        for key in keys {
+            print_empty_line();
+            print_info("id", key.id.as_str());
+            let kind = if key.flags == 257 { "KSK" } else { "ZSK" };
+            print_info("flags", format!("{} ({})", key.flags, kind).as_str());
+            print_info(
+                "algorithm",
+                format!("{} ({})", key.algorithm, algorithm_name(key.algorithm)).as_str(),
+            );
+            if let Some(tag) = key.computed_key_tag {
+                print_info("key tag", tag.to_string().as_str());
+                for ds in key.ds_digests.iter().flatten() {
+                    print_info(
+                        format!("DS {}", digest_type_name(ds.digest_type)).as_str(),
+                        format!("{} {} {} {}", tag, key.algorithm, ds.digest_type, ds.digest)
+                            .as_str(),
+                    );
+                }
+            }
+            print_info("public key", key.public_key.as_str());
+        }
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Show the DNSSEC keys of the domain, with their key tag and DS records");
+        let subcommand = add_fqdn_options(subcommand);
+        add_subcommand_options(subcommand)
+    }
+}
@@ -2,12 +2,16 @@
 
 use clap::{App, ArgMatches, SubCommand};
 use reqwest::RequestBuilder;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::super::super::args::fqdn::add_fqdn_options;
 use super::super::super::command_handler::GandiSubCommandHandler;
 use super::super::super::config::Configuration;
-use super::super::super::display::{add_subcommand_options, print_info, print_list};
+use super::super::super::display::{
+    add_subcommand_options, print_empty_line, print_info, print_list,
+};
+use super::super::super::formatter::Extra;
 
 macro_rules! ROUTE {
     () => {
@@ -15,27 +19,29 @@ macro_rules! ROUTE {
     };
 }
 
-/// Contact information
-#[derive(Debug, Serialize, Deserialize)]
+/// Glue record information
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct GlueRecord {
     /// Fully qualified domain name, written in its native alphabet (IDN).
-    fqdn: String,
+    pub fqdn: String,
     /// Fully qualified domain name, written in unicode.
-    fqdn_unicode: String,
+    pub fqdn_unicode: String,
     /// Name of this host (FQDN without the domain part).
-    name: String,
+    pub name: String,
     /// URL to this host's details.
-    href: String,
+    pub href: String,
     /// List of this host's registered IP addresse.
-    ips: Vec<String>,
+    pub ips: Vec<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Implement the "show domain" subcommand
 pub struct DomainGlueRecordsShowCommand {}
 
 impl GandiSubCommandHandler for DomainGlueRecordsShowCommand {
-    const COMMAND_GROUP: &'static str = "show";
-    const COMMAND: &'static str = "glue-records";
+    const PATH: &'static [&'static str] = &["show", "glue-records"];
+    const READ_ONLY: bool = true;
 
     type Item = Vec<GlueRecord>;
 
@@ -48,7 +54,7 @@ impl GandiSubCommandHandler for DomainGlueRecordsShowCommand {
     /// Display the domain contacts important data
     fn display_human_result(glues: Self::Item) {
         for glue in glues {
-            print!("");
+            print_empty_line();
             print_info("fqdn", glue.fqdn_unicode.as_str());
             print_info("name", glue.name.as_str());
             print_list("ips", &Some(glue.ips));
@@ -57,7 +63,7 @@ impl GandiSubCommandHandler for DomainGlueRecordsShowCommand {
 
     /// Create the clap subcommand with its arguments.
     fn subcommand<'a, 'b>() -> App<'a, 'b> {
-        let subcommand = SubCommand::with_name(Self::COMMAND);
+        let subcommand = SubCommand::with_name(Self::name());
         let subcommand = add_fqdn_options(subcommand);
         add_subcommand_options(subcommand)
     }
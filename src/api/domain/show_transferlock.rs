@@ -0,0 +1,69 @@
+//! [Show the domain transfer lock](https://api.gandi.net/docs/domains/#v5-domain-domains-domain) route binding
+use clap::{App, ArgMatches, SubCommand};
+use reqwest::RequestBuilder;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::config::Configuration;
+use super::super::super::display::{add_subcommand_options, print_flag, print_info};
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}"
+    };
+}
+
+/// Status of a domain that cannot be transferred to another registrar
+pub const TRANSFER_PROHIBITED: &str = "clientTransferProhibited";
+
+/// Transfer lock of a domain, read from the domain information
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TransferLock {
+    pub fqdn: String,
+    /// information assiociated to the tld of the domain, about lock registry support
+    pub can_tld_lock: bool,
+    /// status of the domain, the lock is `clientTransferProhibited`
+    #[serde(default)]
+    pub status: Vec<String>,
+}
+
+impl TransferLock {
+    /// The domain cannot be transferred
+    pub fn locked(&self) -> bool {
+        self.status
+            .iter()
+            .any(|status| status == TRANSFER_PROHIBITED)
+    }
+}
+
+/// Implement the "show transfer-lock" subcommand
+pub struct DomainTransferLockShowCommand {}
+
+impl GandiSubCommandHandler for DomainTransferLockShowCommand {
+    const PATH: &'static [&'static str] = &["show", "transfer-lock"];
+    const READ_ONLY: bool = true;
+    type Item = TransferLock;
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        config.build_req(format!(ROUTE!(), fqdn).as_str())
+    }
+
+    /// Display the lock
+    fn display_human_result(item: Self::Item) {
+        print_info("fqdn", item.fqdn.as_str());
+        print_flag("transfer lock", item.locked());
+        print_flag("registry lock support", item.can_tld_lock);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Show if the domain is locked against transfers");
+        let subcommand = add_fqdn_options(subcommand);
+        add_subcommand_options(subcommand)
+    }
+}
@@ -0,0 +1,247 @@
+//! [Replace the domain tags](https://api.gandi.net/docs/domains/#put-v5-domain-domains-domain-tags) route binding
+use clap::{App, Arg, ArgMatches, SubCommand};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, RequestBuilder};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::command_handler::{send, GandiSubCommandHandler};
+use super::super::super::config::Configuration;
+use super::super::super::display::{add_subcommand_options, print_diff, print_warning};
+use super::super::super::errors::GandiResult;
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/tags"
+    };
+}
+
+/// Body of the update
+#[derive(Debug, Serialize)]
+struct TagsUpdate<'a> {
+    tags: &'a [String],
+}
+
+/// Tags of a domain, before and after the update
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TagsChange {
+    pub fqdn: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+/// Check a tag, it cannot be empty or contain a comma
+fn check_tag(tag: &str) -> Result<(), String> {
+    if tag.trim().is_empty() || tag.contains(',') {
+        Err(format!("invalid tag {:?}", tag))
+    } else {
+        Ok(())
+    }
+}
+
+/// Tags of the command line, without duplicates
+fn given_tags(params: &ArgMatches) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in params.values_of("TAGS").into_iter().flatten() {
+        let tag = tag.trim().to_string();
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Current tags, followed by the missing tags of the command line
+fn added_tags(current: &[String], params: &ArgMatches) -> Vec<String> {
+    let mut tags = current.to_vec();
+    tags.extend(
+        given_tags(params)
+            .into_iter()
+            .filter(|tag| !current.contains(tag)),
+    );
+    tags
+}
+
+/// Current tags, without the tags of the command line
+fn remaining_tags(current: &[String], params: &ArgMatches) -> Vec<String> {
+    let removed = given_tags(params);
+    current
+        .iter()
+        .filter(|tag| !removed.contains(tag))
+        .cloned()
+        .collect()
+}
+
+/// Fetch the tags of the domain
+fn fetch_tags(config: &Configuration, fqdn: &str) -> GandiResult<Vec<String>> {
+    let item = send(config.build_req(format!(ROUTE!(), fqdn).as_str()))?.json()?;
+    Ok(item)
+}
+
+/// Build the request that replaces the tags of the domain
+fn build_update_req(config: &Configuration, fqdn: &str, tags: &[String]) -> RequestBuilder {
+    config
+        .build_req_with_method(Method::PUT, format!(ROUTE!(), fqdn).as_str())
+        .json(&TagsUpdate { tags })
+}
+
+/// Build the request that replaces the tags of the domain by the result of
+/// `update` on the current ones. If they cannot be fetched, the request of
+/// the current tags is returned: nothing is written, and sending it reports
+/// the error.
+fn build_current_update_req<F>(
+    config: &Configuration,
+    params: &ArgMatches,
+    update: F,
+) -> RequestBuilder
+where
+    F: FnOnce(&[String]) -> Vec<String>,
+{
+    let fqdn = params.value_of("FQDN").unwrap();
+    match fetch_tags(config, fqdn) {
+        Ok(current) => build_update_req(config, fqdn, &update(&current)),
+        Err(_) => config.build_req(format!(ROUTE!(), fqdn).as_str()),
+    }
+}
+
+/// Replace the tags of the domain by the result of `update` on the current
+/// ones, and report them before and after. Nothing is sent if they are unchanged.
+fn process_update<H, F>(config: &Configuration, params: &ArgMatches, update: F) -> GandiResult<()>
+where
+    H: GandiSubCommandHandler<Item = TagsChange>,
+    F: FnOnce(&[String]) -> Vec<String>,
+{
+    let fqdn = params.value_of("FQDN").unwrap();
+    let before = fetch_tags(config, fqdn)?;
+    let tags = update(&before);
+    let after = if tags == before {
+        before.clone()
+    } else {
+        send(build_update_req(config, fqdn, &tags))?;
+        fetch_tags(config, fqdn)?
+    };
+    let change = TagsChange {
+        fqdn: fqdn.to_string(),
+        before,
+        after,
+    };
+    H::output(change, &HeaderMap::new(), params)
+}
+
+/// Create the clap subcommand of a tags update
+fn tags_subcommand<'a, 'b>(name: &'a str, about: &'b str, required: bool) -> App<'a, 'b> {
+    let subcommand = SubCommand::with_name(name).about(about).arg(
+        Arg::with_name("TAGS")
+            .index(2)
+            .multiple(true)
+            .required(required)
+            .validator(|tag| check_tag(&tag))
+            .help("Tags of the domain"),
+    );
+    let subcommand = add_fqdn_options(subcommand);
+    add_subcommand_options(subcommand)
+}
+
+/// Implement the "tag add" subcommand
+pub struct DomainTagsAddCommand {}
+
+impl GandiSubCommandHandler for DomainTagsAddCommand {
+    const PATH: &'static [&'static str] = &["tag", "add"];
+    const READ_ONLY: bool = false;
+    type Item = TagsChange;
+
+    /// Create the route with the current tags and the added ones
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        build_current_update_req(config, params, |current| added_tags(current, params))
+    }
+
+    /// Add the missing tags after the current ones
+    fn process(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        process_update::<Self, _>(config, params, |current| added_tags(current, params))
+    }
+
+    /// Display the diff of the tags
+    fn display_human_result(item: Self::Item) {
+        print_diff("tags", &item.before, &item.after);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        tags_subcommand(Self::name(), "Add tags to a domain", true)
+    }
+}
+
+/// Implement the "tag remove" subcommand
+pub struct DomainTagsRemoveCommand {}
+
+impl GandiSubCommandHandler for DomainTagsRemoveCommand {
+    const PATH: &'static [&'static str] = &["tag", "remove"];
+    const READ_ONLY: bool = false;
+    type Item = TagsChange;
+
+    /// Create the route with the current tags, without the removed ones
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        build_current_update_req(config, params, |current| remaining_tags(current, params))
+    }
+
+    /// Remove the tags, the unknown ones are reported
+    fn process(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        process_update::<Self, _>(config, params, |current| {
+            for tag in given_tags(params)
+                .iter()
+                .filter(|tag| !current.contains(tag))
+            {
+                print_warning(format!("{} is not a tag of the domain", tag).as_str());
+            }
+            remaining_tags(current, params)
+        })
+    }
+
+    /// Display the diff of the tags
+    fn display_human_result(item: Self::Item) {
+        print_diff("tags", &item.before, &item.after);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        tags_subcommand(Self::name(), "Remove tags from a domain", true)
+    }
+}
+
+/// Implement the "tag set" subcommand
+pub struct DomainTagsSetCommand {}
+
+impl GandiSubCommandHandler for DomainTagsSetCommand {
+    const PATH: &'static [&'static str] = &["tag", "set"];
+    const READ_ONLY: bool = false;
+    type Item = TagsChange;
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        build_update_req(
+            config,
+            params.value_of("FQDN").unwrap(),
+            &given_tags(params),
+        )
+    }
+
+    /// Replace the tags
+    fn process(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        process_update::<Self, _>(config, params, |_| given_tags(params))
+    }
+
+    /// Display the diff of the tags
+    fn display_human_result(item: Self::Item) {
+        print_diff("tags", &item.before, &item.after);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        tags_subcommand(
+            Self::name(),
+            "Replace the tags of a domain, they are removed if none is given",
+            false,
+        )
+    }
+}
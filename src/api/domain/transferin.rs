@@ -0,0 +1,127 @@
+//! [Transfer a domain to Gandi](https://api.gandi.net/docs/domains/#post-v5-domain-transferin) route binding
+use clap::{App, Arg, ArgMatches, SubCommand};
+use reqwest::{Method, RequestBuilder};
+use serde::Serialize;
+
+use super::super::super::args::confirm::{add_subcommand_options as add_confirm_options, confirm};
+use super::super::super::args::contacts::{
+    add_subcommand_options as add_contacts_options, ContactFiles,
+};
+use super::super::super::args::duration::{
+    add_subcommand_options as add_duration_options, DomainDuration,
+};
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::args::sharing_id::{
+    add_subcommand_options as add_sharing_id_options, SharingSpace,
+};
+use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::config::Configuration;
+use super::super::super::display::{add_subcommand_options, print_notice};
+use super::super::super::errors::GandiResult;
+use super::super::operation::{print_operation, Operation};
+use super::check::{quote, Process};
+use super::show_contacts::Contact;
+
+pub const ROUTE: &str = "/v5/domain/transferin";
+
+/// Body of the transfer
+#[derive(Debug, Serialize)]
+struct DomainTransfer {
+    fqdn: String,
+    /// authorization code given by the current registrar
+    authinfo: String,
+    /// number of years added to the registration
+    duration: usize,
+    owner: Contact,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    admin: Option<Contact>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tech: Option<Contact>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bill: Option<Contact>,
+    /// nameservers are kept if not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nameservers: Option<Vec<String>>,
+}
+
+/// Implement the "transfer-in" subcommand
+pub struct DomainTransferInCommand {}
+
+impl GandiSubCommandHandler for DomainTransferInCommand {
+    const PATH: &'static [&'static str] = &["transfer-in"];
+    const READ_ONLY: bool = false;
+    type Item = Operation;
+
+    /// Check the domain can be transferred, display the price, and ask
+    /// for a confirmation
+    fn prepare(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let duration = DomainDuration::from(params);
+        let sharing_space = SharingSpace::from(params);
+        let quote = quote(
+            config,
+            fqdn,
+            &sharing_space,
+            Process::Transfer,
+            duration.years,
+        )?;
+        print_notice(quote.to_string().as_str());
+        confirm(params, format!("Transfer {} to Gandi?", fqdn).as_str())
+    }
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let contacts = ContactFiles::from(params);
+        let body = DomainTransfer {
+            fqdn: params.value_of("FQDN").unwrap().to_string(),
+            authinfo: params.value_of("AUTHINFO").unwrap().to_string(),
+            duration: DomainDuration::from(params).years,
+            // required by clap
+            owner: contacts.owner.unwrap(),
+            admin: contacts.admin,
+            tech: contacts.tech,
+            bill: contacts.bill,
+            nameservers: params
+                .values_of("NAMESERVERS")
+                .map(|nameservers| nameservers.map(str::to_string).collect()),
+        };
+        let req = config
+            .build_req_with_method(Method::POST, ROUTE)
+            .json(&body);
+        SharingSpace::from(params).build_req(req)
+    }
+
+    /// Display the operation
+    fn display_human_result(item: Self::Item) {
+        print_operation(&item);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Transfer a domain from another registrar, the price is displayed before")
+            .arg(
+                Arg::with_name("AUTHINFO")
+                    .long("authinfo")
+                    .takes_value(true)
+                    .required(true)
+                    .value_name("CODE")
+                    .help("Authorization code given by the current registrar"),
+            )
+            .arg(
+                Arg::with_name("NAMESERVERS")
+                    .long("nameservers")
+                    .takes_value(true)
+                    .multiple(true)
+                    .use_delimiter(true)
+                    .value_name("NS")
+                    .help("Nameservers of the domain, the current ones are kept if not set"),
+            );
+        let subcommand = add_fqdn_options(subcommand);
+        let subcommand = add_duration_options(subcommand);
+        let subcommand = add_contacts_options(subcommand, true);
+        let subcommand = add_sharing_id_options(subcommand);
+        let subcommand = add_confirm_options(subcommand);
+        add_subcommand_options(subcommand)
+    }
+}
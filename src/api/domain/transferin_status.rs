@@ -0,0 +1,179 @@
+//! [Transfer status](https://api.gandi.net/docs/domains/#get-v5-domain-transferin-domain) route binding
+use std::thread;
+
+use chrono::{DateTime, Utc};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use reqwest::header::HeaderMap;
+use reqwest::RequestBuilder;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::args::watch::parse_interval;
+use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::config::Configuration;
+use super::super::super::display::{
+    add_subcommand_options, print_date, print_info, print_notice_info,
+};
+use super::super::super::errors::{GandiError, GandiResult};
+use super::super::super::formatter::date_formatter;
+use super::super::super::formatter::Extra;
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/transferin/{}"
+    };
+}
+
+/// Step of the transfer once it succeeded
+const DONE_STEP: &str = "done";
+
+/// Step of the transfer once it failed
+const ERROR_STEP: &str = "error";
+
+/// Form of Authorization sent to the contacts
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Foa {
+    pub email: String,
+    /// answer of the contact, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// State of an inbound transfer
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TransferIn {
+    pub fqdn: String,
+    /// name of the current step
+    pub step: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step_number: Option<usize>,
+    /// the reason of the failure, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errortype: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errortype_label: Option<String>,
+    #[serde(default)]
+    pub foa: Vec<Foa>,
+    #[serde(with = "date_formatter")]
+    #[schemars(schema_with = "date_formatter::schema")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "date_formatter")]
+    #[schemars(schema_with = "date_formatter::schema")]
+    pub updated_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl TransferIn {
+    /// The transfer failed
+    pub fn failed(&self) -> bool {
+        self.errortype.is_some() || self.step == ERROR_STEP
+    }
+
+    /// The transfer is over, it succeeded or failed
+    pub fn finished(&self) -> bool {
+        self.step == DONE_STEP || self.failed()
+    }
+
+    /// Label of the current step, e.g. `waiting_foa (2)`
+    fn step_label(&self) -> String {
+        match self.step_number {
+            Some(number) => format!("{} ({})", self.step, number),
+            None => self.step.clone(),
+        }
+    }
+}
+
+/// Implement the "transfer-in status" subcommand
+pub struct DomainTransferInStatusCommand {}
+
+impl GandiSubCommandHandler for DomainTransferInStatusCommand {
+    const PATH: &'static [&'static str] = &["transfer-in", "status"];
+    const READ_ONLY: bool = true;
+    // polled until the transfer is over, see `process`
+    const WATCH: bool = false;
+    type Item = TransferIn;
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        config.build_req(format!(ROUTE!(), fqdn).as_str())
+    }
+
+    /// Poll the transfer until it is over, the step changes are reported
+    /// on stderr.
+    fn process(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        // checked by the clap validator
+        let interval = parse_interval(params.value_of("INTERVAL").unwrap()).unwrap();
+        let mut step = String::new();
+        let transfer = loop {
+            let transfer = Self::fetch(config, params)?;
+            if params.is_present("ONCE") || transfer.finished() {
+                break transfer;
+            }
+            if transfer.step != step {
+                print_notice_info(transfer.fqdn.as_str(), transfer.step_label().as_str());
+                step = transfer.step.clone();
+            }
+            thread::sleep(interval);
+        };
+        let failure = if transfer.failed() {
+            Some(GandiError::TransferFailed(
+                transfer.fqdn.clone(),
+                transfer
+                    .errortype_label
+                    .clone()
+                    .or_else(|| transfer.errortype.clone())
+                    .unwrap_or_else(|| transfer.step.clone()),
+            ))
+        } else {
+            None
+        };
+        Self::output(transfer, &HeaderMap::new(), params)?;
+        match failure {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Display the state of the transfer
+    fn display_human_result(item: Self::Item) {
+        print_info("fqdn", item.fqdn.as_str());
+        print_info("step", item.step_label().as_str());
+        if let Some(error) = item.errortype_label.as_ref().or(item.errortype.as_ref()) {
+            print_info("error", error.as_str());
+        }
+        for foa in item.foa.iter() {
+            print_info(
+                format!("foa {}", foa.email).as_str(),
+                foa.answer.as_deref().unwrap_or("no answer"),
+            );
+        }
+        print_date("created at", Some(&item.created_at));
+        print_date("updated at", Some(&item.updated_at));
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Display the state of a transfer, polled until it is over")
+            .arg(
+                Arg::with_name("INTERVAL")
+                    .long("interval")
+                    .takes_value(true)
+                    .default_value("30s")
+                    .validator(|interval| parse_interval(&interval).map(|_| ()))
+                    .help("Delay between two polls, e.g. 30s or 5m"),
+            )
+            .arg(
+                Arg::with_name("ONCE")
+                    .long("once")
+                    .help("Display the current state, without polling"),
+            );
+        let subcommand = add_fqdn_options(subcommand);
+        add_subcommand_options(subcommand)
+    }
+}
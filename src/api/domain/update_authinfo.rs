@@ -0,0 +1,65 @@
+//! [Reset the domain authinfo](https://api.gandi.net/docs/domains/#post-v5-domain-domains-domain-authinfo) route binding
+use clap::{App, ArgMatches, SubCommand};
+use reqwest::{Method, RequestBuilder};
+
+use super::super::super::args::confirm::{add_subcommand_options as add_confirm_options, confirm};
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::command_handler::{send, GandiSubCommandHandler};
+use super::super::super::config::Configuration;
+use super::super::super::display::add_subcommand_options;
+use super::super::super::errors::GandiResult;
+use super::show_authinfo::{
+    add_reveal_option, output_authinfo, Authinfo, DomainAuthinfoShowCommand,
+};
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/authinfo"
+    };
+}
+
+/// Implement the "update authinfo" subcommand
+pub struct DomainAuthinfoUpdateCommand {}
+
+impl GandiSubCommandHandler for DomainAuthinfoUpdateCommand {
+    const PATH: &'static [&'static str] = &["update", "authinfo"];
+    const READ_ONLY: bool = false;
+    type Item = Authinfo;
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        config.build_req_with_method(Method::POST, format!(ROUTE!(), fqdn).as_str())
+    }
+
+    /// Regenerate the authinfo, and display the new one
+    fn process(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let fqdn = params.value_of("FQDN").unwrap();
+        confirm(
+            params,
+            format!(
+                "Regenerate the authinfo of {}? The current one will not be valid anymore.",
+                fqdn
+            )
+            .as_str(),
+        )?;
+        send(Self::build_req(config, params))?;
+        let item = DomainAuthinfoShowCommand::fetch(config, params)?;
+        output_authinfo::<Self>(item, params)
+    }
+
+    /// Display the new authinfo
+    fn display_human_result(item: Self::Item) {
+        DomainAuthinfoShowCommand::display_human_result(item);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Regenerate the authinfo code used to transfer the domain to another registrar");
+        let subcommand = add_fqdn_options(subcommand);
+        let subcommand = add_reveal_option(subcommand);
+        let subcommand = add_confirm_options(subcommand);
+        add_subcommand_options(subcommand)
+    }
+}
@@ -0,0 +1,270 @@
+//! [Update the domain autorenew](https://api.gandi.net/docs/domains/#patch-v5-domain-domains-domain-autorenew) route binding
+use clap::{App, Arg, ArgGroup, ArgMatches, SubCommand};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, RequestBuilder};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::super::super::args::confirm::{add_subcommand_options as add_confirm_options, confirm};
+use super::super::super::args::domain_filter::DomainFilter;
+use super::super::super::args::duration::parse_years;
+use super::super::super::args::sharing_id::{
+    add_subcommand_options as add_sharing_id_options, SharingSpace,
+};
+use super::super::super::command_handler::{send, GandiSubCommandHandler};
+use super::super::super::config::Configuration;
+use super::super::super::display::{
+    add_subcommand_options, print_empty_line, print_info, print_notice_info, print_warning,
+};
+use super::super::super::errors::{GandiError, GandiResult};
+use super::list::DomainListCommand;
+use super::show::{Autorenew, DomainShowCommand};
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/autorenew"
+    };
+}
+
+/// Body of the update, unset fields are left unchanged
+#[derive(Debug, Serialize)]
+struct AutorenewPolicy {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+    /// number of years
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration: Option<usize>,
+    /// sharing_id that pay the renew
+    #[serde(skip_serializing_if = "Option::is_none")]
+    org_id: Option<String>,
+}
+
+/// Retrieve the policy from the clap subcommand arguments
+impl<'a> From<&'a ArgMatches<'a>> for AutorenewPolicy {
+    fn from(params: &ArgMatches<'a>) -> Self {
+        let enabled = if params.is_present("ENABLE") {
+            Some(true)
+        } else if params.is_present("DISABLE") {
+            Some(false)
+        } else {
+            None
+        };
+        AutorenewPolicy {
+            enabled,
+            // checked by the clap validator
+            duration: params
+                .value_of("AUTORENEW_DURATION")
+                .map(|years| parse_years(years).unwrap()),
+            org_id: params.value_of("ORG").map(str::to_string),
+        }
+    }
+}
+
+/// Autorenew of a domain, before and after the update
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AutorenewChange {
+    pub fqdn: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Autorenew>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Autorenew>,
+    /// the reason the domain has not been updated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Autorenew in the human format, e.g. `enabled, 1 year(s), paid by ORG_ID`
+fn autorenew_label(autorenew: Option<&Autorenew>) -> String {
+    match autorenew {
+        Some(autorenew) => format!(
+            "{}, {} year(s), paid by {}",
+            if autorenew.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            },
+            autorenew.duration,
+            autorenew.org_id.as_deref().unwrap_or("the owner")
+        ),
+        None => "unknown".to_string(),
+    }
+}
+
+/// Fetch the autorenew of the domain, it is reported as unknown if the
+/// domain cannot be fetched.
+fn fetch_autorenew(config: &Configuration, fqdn: &str) -> Option<Autorenew> {
+    let domain = DomainShowCommand::params_from(&[fqdn])
+        .and_then(|params| DomainShowCommand::fetch(config, &params));
+    match domain {
+        Ok(domain) => Some(domain.autorenew),
+        Err(err) => {
+            print_warning(format!("{}: autorenew unknown, {}", fqdn, err).as_str());
+            None
+        }
+    }
+}
+
+/// Build the update request of the domain
+fn build_update_req(
+    config: &Configuration,
+    fqdn: &str,
+    policy: &AutorenewPolicy,
+) -> RequestBuilder {
+    config
+        .build_req_with_method(Method::PATCH, format!(ROUTE!(), fqdn).as_str())
+        .json(policy)
+}
+
+/// Update the autorenew of the domain, with its state before and after.
+/// Only a failed update is an error.
+fn update(config: &Configuration, fqdn: &str, policy: &AutorenewPolicy) -> AutorenewChange {
+    let before = fetch_autorenew(config, fqdn);
+    match send(build_update_req(config, fqdn, policy)) {
+        Ok(_) => AutorenewChange {
+            fqdn: fqdn.to_string(),
+            before,
+            after: fetch_autorenew(config, fqdn),
+            error: None,
+        },
+        Err(err) => AutorenewChange {
+            fqdn: fqdn.to_string(),
+            before,
+            after: None,
+            error: Some(format!("{}", err)),
+        },
+    }
+}
+
+/// Domains of the bulk update, matching the tld and the tag
+fn matching_domains(config: &Configuration, params: &ArgMatches) -> GandiResult<Vec<String>> {
+    let filter = DomainFilter {
+        tld: params
+            .value_of("TLD")
+            .map(|tld| tld.trim_start_matches('.').to_string()),
+        tag: params.value_of("TAG").map(str::to_string),
+        ..DomainFilter::default()
+    };
+    let domains = DomainListCommand::fetch_all(config, &filter, &SharingSpace::from(params))?;
+    Ok(domains.into_iter().map(|domain| domain.fqdn).collect())
+}
+
+/// Implement the "update autorenew" subcommand
+pub struct DomainAutorenewUpdateCommand {}
+
+impl GandiSubCommandHandler for DomainAutorenewUpdateCommand {
+    const PATH: &'static [&'static str] = &["update", "autorenew"];
+    const READ_ONLY: bool = false;
+    type Item = Vec<AutorenewChange>;
+
+    /// Create the route of a single domain, see `process` for the bulk update
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        build_update_req(config, fqdn, &AutorenewPolicy::from(params))
+    }
+
+    /// Update the domain, or every matching domains, and report the changes.
+    fn process(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let policy = AutorenewPolicy::from(params);
+        let fqdns = match params.value_of("FQDN") {
+            Some(fqdn) => vec![fqdn.to_string()],
+            None => {
+                let fqdns = matching_domains(config, params)?;
+                print_notice_info(
+                    format!("{} matching domain(s)", fqdns.len()).as_str(),
+                    fqdns.join(", ").as_str(),
+                );
+                confirm(params, "Update the autorenew of these domains?")?;
+                fqdns
+            }
+        };
+        let changes: Self::Item = fqdns
+            .iter()
+            .map(|fqdn| update(config, fqdn, &policy))
+            .collect();
+        let failed = changes
+            .iter()
+            .filter(|change| change.error.is_some())
+            .count();
+        let total = changes.len();
+        Self::output(changes, &HeaderMap::new(), params)?;
+        if failed > 0 {
+            Err(GandiError::BulkError(failed, total))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Display the autorenew of every domains, before and after
+    fn display_human_result(items: Self::Item) {
+        for change in items {
+            print_empty_line();
+            print_info("fqdn", change.fqdn.as_str());
+            print_info("before", autorenew_label(change.before.as_ref()).as_str());
+            match change.error {
+                Some(error) => print_info("error", error.as_str()),
+                None => print_info("after", autorenew_label(change.after.as_ref()).as_str()),
+            }
+        }
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Update the autorenew of a domain, or of every domains of a tag or a tld")
+            .arg(
+                Arg::with_name("FQDN")
+                    .index(1)
+                    .required_unless_one(&["TAG", "TLD"])
+                    .conflicts_with_all(&["TAG", "TLD"])
+                    .help("domain name to update"),
+            )
+            .arg(
+                Arg::with_name("TAG")
+                    .long("tag")
+                    .takes_value(true)
+                    .help("Update every domains with this tag"),
+            )
+            .arg(
+                Arg::with_name("TLD")
+                    .long("tld")
+                    .takes_value(true)
+                    .help("Update every domains of this top level domain"),
+            )
+            .arg(
+                Arg::with_name("ENABLE")
+                    .long("enable")
+                    .help("Enable the autorenew"),
+            )
+            .arg(
+                Arg::with_name("DISABLE")
+                    .long("disable")
+                    .help("Disable the autorenew"),
+            )
+            .arg(
+                Arg::with_name("AUTORENEW_DURATION")
+                    .short("d")
+                    .long("duration")
+                    .takes_value(true)
+                    .value_name("YEARS")
+                    .validator(|years| parse_years(&years).map(|_| ()))
+                    .help("Number of years of every renewal"),
+            )
+            .arg(
+                Arg::with_name("ORG")
+                    .long("org")
+                    .takes_value(true)
+                    .value_name("SHARING_ID")
+                    .help("Organization that pays the renewals"),
+            )
+            .group(ArgGroup::with_name("STATE").args(&["ENABLE", "DISABLE"]))
+            .group(
+                ArgGroup::with_name("POLICY")
+                    .args(&["ENABLE", "DISABLE", "AUTORENEW_DURATION", "ORG"])
+                    .multiple(true)
+                    .required(true),
+            );
+        let subcommand = add_sharing_id_options(subcommand);
+        let subcommand = add_confirm_options(subcommand);
+        add_subcommand_options(subcommand)
+    }
+}
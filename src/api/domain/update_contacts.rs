@@ -0,0 +1,164 @@
+//! [Update the domain contacts](https://api.gandi.net/docs/domains/#patch-v5-domain-domains-domain-contacts) route binding
+use clap::{App, Arg, ArgGroup, ArgMatches, SubCommand};
+use reqwest::{Method, RequestBuilder};
+use serde::Serialize;
+
+use super::super::super::args::contacts::{
+    add_subcommand_options as add_contacts_options, validate, writable, ContactFiles, ROLES,
+};
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::command_handler::{send, GandiSubCommandHandler};
+use super::super::super::config::Configuration;
+use super::super::super::display::add_subcommand_options;
+use super::super::super::errors::{GandiError, GandiResult};
+use super::super::operation::{print_operation, Operation};
+use super::show_contacts::{Contact, DomainContactsShowCommand};
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/contacts"
+    };
+}
+
+/// Body of the update, the owner is changed by another process
+#[derive(Debug, Serialize)]
+struct ContactsUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    admin: Option<Contact>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tech: Option<Contact>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bill: Option<Contact>,
+}
+
+impl ContactsUpdate {
+    /// Set the contact of a role that can be updated
+    fn set(&mut self, role: &str, contact: Contact) {
+        match role {
+            "admin" => self.admin = Some(contact),
+            "tech" => self.tech = Some(contact),
+            "bill" => self.bill = Some(contact),
+            _ => {}
+        }
+    }
+}
+
+/// The update of the contact files
+impl From<ContactFiles> for ContactsUpdate {
+    fn from(files: ContactFiles) -> Self {
+        ContactsUpdate {
+            admin: files.admin,
+            tech: files.tech,
+            bill: files.bill,
+        }
+    }
+}
+
+/// Build the update request of the domain
+fn build_update_req(
+    config: &Configuration,
+    params: &ArgMatches,
+    update: &ContactsUpdate,
+) -> RequestBuilder {
+    let fqdn = params.value_of("FQDN").unwrap();
+    config
+        .build_req_with_method(Method::PATCH, format!(ROUTE!(), fqdn).as_str())
+        .json(update)
+}
+
+/// Implement the "update contacts" subcommand
+pub struct DomainContactsUpdateCommand {}
+
+impl GandiSubCommandHandler for DomainContactsUpdateCommand {
+    const PATH: &'static [&'static str] = &["update", "contacts"];
+    const READ_ONLY: bool = false;
+    type Item = Operation;
+
+    /// Create the route with the contact files, see `process` for the copies
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let update = ContactsUpdate::from(ContactFiles::from(params));
+        build_update_req(config, params, &update)
+    }
+
+    /// Update the contacts, the copied role is taken from its file if any,
+    /// or from the current contacts of the domain.
+    fn process(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let files = ContactFiles::from(params);
+        let copy = match params.value_of("COPY") {
+            Some(role) => {
+                let contact = match files.get(role) {
+                    Some(contact) => contact.clone(),
+                    None => {
+                        let fqdn = params.value_of("FQDN").unwrap();
+                        let contacts = DomainContactsShowCommand::fetch(
+                            config,
+                            &DomainContactsShowCommand::params_from(&[fqdn])?,
+                        )?;
+                        // the role is checked by clap
+                        contacts.get(role).cloned().unwrap()
+                    }
+                };
+                // the contacts of the domain are checked as the files are
+                validate(&contact)
+                    .map_err(|err| GandiError::InvalidContact(format!("{}: {}", role, err)))?;
+                Some(writable(contact))
+            }
+            None => None,
+        };
+        let mut update = ContactsUpdate::from(files);
+        if let Some(contact) = copy {
+            for role in params.values_of("TO").unwrap() {
+                if params.value_of(role.to_uppercase()).is_some() {
+                    return Err(GandiError::ClapError(format!(
+                        "--{} cannot be used with --to {}",
+                        role, role
+                    )));
+                }
+                update.set(role, contact.clone());
+            }
+        }
+
+        let mut resp = send(build_update_req(config, params, &update))?;
+        let item: Self::Item = resp.json()?;
+        Self::output(item, resp.headers(), params)
+    }
+
+    /// Display the operation
+    fn display_human_result(item: Self::Item) {
+        print_operation(&item);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Update the admin, tech and bill contacts of a domain")
+            .arg(
+                Arg::with_name("COPY")
+                    .long("copy")
+                    .takes_value(true)
+                    .value_name("ROLE")
+                    .possible_values(&ROLES)
+                    .requires("TO")
+                    .help("Copy the contact of this role, from its file or from the domain"),
+            )
+            .arg(
+                Arg::with_name("TO")
+                    .long("to")
+                    .takes_value(true)
+                    .value_name("ROLE")
+                    .multiple(true)
+                    .use_delimiter(true)
+                    .possible_values(&ROLES[1..])
+                    .requires("COPY")
+                    .help("Roles the copied contact is set to"),
+            );
+        let subcommand = add_fqdn_options(subcommand);
+        let subcommand = add_contacts_options(subcommand, false).group(
+            ArgGroup::with_name("CONTACTS")
+                .args(&["ADMIN", "TECH", "BILL", "COPY"])
+                .multiple(true)
+                .required(true),
+        );
+        add_subcommand_options(subcommand)
+    }
+}
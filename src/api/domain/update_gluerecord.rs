@@ -0,0 +1,60 @@
+//! [Update a glue record](https://api.gandi.net/docs/domains/#put-v5-domain-domains-domain-hosts-name) route binding
+use clap::{App, ArgMatches, SubCommand};
+use reqwest::{Method, RequestBuilder};
+use serde::Serialize;
+
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::args::glue_record::{
+    add_subcommand_options as add_glue_record_options, glue_record_ips,
+};
+use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::config::Configuration;
+use super::super::super::display::add_subcommand_options;
+use super::super::operation::{print_operation, Operation};
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/hosts/{}"
+    };
+}
+
+/// Body of the update, the addresses replace the current ones
+#[derive(Debug, Serialize)]
+struct GlueRecordUpdate {
+    ips: Vec<String>,
+}
+
+/// Implement the "update glue-record" subcommand
+pub struct DomainGlueRecordUpdateCommand {}
+
+impl GandiSubCommandHandler for DomainGlueRecordUpdateCommand {
+    const PATH: &'static [&'static str] = &["update", "glue-record"];
+    const READ_ONLY: bool = false;
+    type Item = Operation;
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let name = params.value_of("NAME").unwrap();
+        let body = GlueRecordUpdate {
+            ips: glue_record_ips(params),
+        };
+        config
+            .build_req_with_method(Method::PUT, format!(ROUTE!(), fqdn, name).as_str())
+            .json(&body)
+    }
+
+    /// Display the operation
+    fn display_human_result(item: Self::Item) {
+        print_operation(&item);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Replace the addresses of a host of the domain");
+        let subcommand = add_fqdn_options(subcommand);
+        let subcommand = add_glue_record_options(subcommand, true);
+        add_subcommand_options(subcommand)
+    }
+}
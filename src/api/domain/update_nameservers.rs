@@ -0,0 +1,154 @@
+//! [Update the domain nameservers](https://api.gandi.net/docs/domains/#put-v5-domain-domains-domain-nameservers) route binding
+use clap::{App, Arg, ArgMatches, SubCommand};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, RequestBuilder};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::super::super::args::fqdn::{check_hostname, normalize_hostname};
+use super::super::super::command_handler::{send, GandiSubCommandHandler};
+use super::super::super::config::Configuration;
+use super::super::super::display::{add_subcommand_options, print_diff, print_warning};
+use super::super::super::errors::GandiResult;
+use super::show_gluerecords::DomainGlueRecordsShowCommand;
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/nameservers"
+    };
+}
+
+macro_rules! LIVEDNS_ROUTE {
+    () => {
+        "/v5/domain/domains/{}/livedns"
+    };
+}
+
+/// Body of the update
+#[derive(Debug, Serialize)]
+struct NameserversUpdate {
+    nameservers: Vec<String>,
+}
+
+/// Nameservers of a domain, before and after the update
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NameserversChange {
+    pub fqdn: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+/// Nameservers of the command line, lowercased and without trailing dot
+fn nameservers(params: &ArgMatches) -> Vec<String> {
+    params
+        .values_of("NAMESERVERS")
+        .map(|nameservers| nameservers.map(normalize_hostname).collect())
+        .unwrap_or_default()
+}
+
+/// Fetch the nameservers of the domain
+pub fn fetch_nameservers(config: &Configuration, fqdn: &str) -> GandiResult<Vec<String>> {
+    let item = send(config.build_req(format!(ROUTE!(), fqdn).as_str()))?.json()?;
+    Ok(item)
+}
+
+/// Warn about the nameservers inside the domain that have no glue record,
+/// they cannot be resolved.
+fn check_glue_records(
+    config: &Configuration,
+    fqdn: &str,
+    nameservers: &[String],
+) -> GandiResult<()> {
+    let suffix = format!(".{}", normalize_hostname(fqdn));
+    let inner: Vec<String> = nameservers
+        .iter()
+        .map(|ns| normalize_hostname(ns))
+        .filter(|ns| ns.ends_with(suffix.as_str()))
+        .collect();
+    if inner.is_empty() {
+        return Ok(());
+    }
+    let glues = DomainGlueRecordsShowCommand::fetch(
+        config,
+        &DomainGlueRecordsShowCommand::params_from(&[fqdn])?,
+    )?;
+    for ns in inner {
+        if !glues
+            .iter()
+            .any(|glue| normalize_hostname(&glue.fqdn) == ns)
+        {
+            print_warning(format!("{} has no glue record, it cannot be resolved", ns).as_str());
+        }
+    }
+    Ok(())
+}
+
+/// Implement the "update nameservers" subcommand
+pub struct DomainNameserversUpdateCommand {}
+
+impl GandiSubCommandHandler for DomainNameserversUpdateCommand {
+    const PATH: &'static [&'static str] = &["update", "nameservers"];
+    const READ_ONLY: bool = false;
+    type Item = NameserversChange;
+
+    /// Create the route, LiveDNS has its own route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        if params.is_present("LIVEDNS") {
+            config.build_req_with_method(Method::POST, format!(LIVEDNS_ROUTE!(), fqdn).as_str())
+        } else {
+            let body = NameserversUpdate {
+                nameservers: nameservers(params),
+            };
+            config
+                .build_req_with_method(Method::PUT, format!(ROUTE!(), fqdn).as_str())
+                .json(&body)
+        }
+    }
+
+    /// Update the nameservers, and report them before and after
+    fn process(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let before = fetch_nameservers(config, fqdn)?;
+        check_glue_records(config, fqdn, &nameservers(params))?;
+        send(Self::build_req(config, params))?;
+        let change = NameserversChange {
+            fqdn: fqdn.to_string(),
+            before,
+            after: fetch_nameservers(config, fqdn)?,
+        };
+        Self::output(change, &HeaderMap::new(), params)
+    }
+
+    /// Display the diff of the nameservers
+    fn display_human_result(item: Self::Item) {
+        print_diff("nameservers", &item.before, &item.after);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Update the nameservers of a domain, or switch it to LiveDNS")
+            .arg(
+                Arg::with_name("FQDN")
+                    .index(1)
+                    .required(true)
+                    .help("domain name to update"),
+            )
+            .arg(
+                Arg::with_name("NAMESERVERS")
+                    .index(2)
+                    .multiple(true)
+                    .required_unless("LIVEDNS")
+                    .validator(|ns| check_hostname(&ns))
+                    .help("Host names of the nameservers"),
+            )
+            .arg(
+                Arg::with_name("LIVEDNS")
+                    .long("livedns")
+                    .conflicts_with("NAMESERVERS")
+                    .help("Enable LiveDNS and use its nameservers"),
+            );
+        add_subcommand_options(subcommand)
+    }
+}
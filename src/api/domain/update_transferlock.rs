@@ -0,0 +1,65 @@
+//! [Update the domain transfer lock](https://api.gandi.net/docs/domains/#patch-v5-domain-domains-domain-status) route binding
+use std::collections::HashMap;
+
+use clap::{App, Arg, ArgGroup, ArgMatches, SubCommand};
+use reqwest::{Method, RequestBuilder};
+
+use super::super::super::args::fqdn::add_fqdn_options;
+use super::super::super::command_handler::GandiSubCommandHandler;
+use super::super::super::config::Configuration;
+use super::super::super::display::add_subcommand_options;
+use super::super::operation::{print_operation, Operation};
+use super::show_transferlock::TRANSFER_PROHIBITED;
+
+macro_rules! ROUTE {
+    () => {
+        "/v5/domain/domains/{}/status"
+    };
+}
+
+/// Implement the "update transfer-lock" subcommand
+pub struct DomainTransferLockUpdateCommand {}
+
+impl GandiSubCommandHandler for DomainTransferLockUpdateCommand {
+    const PATH: &'static [&'static str] = &["update", "transfer-lock"];
+    const READ_ONLY: bool = false;
+    type Item = Operation;
+
+    /// Create the route
+    fn build_req(config: &Configuration, params: &ArgMatches) -> RequestBuilder {
+        let fqdn = params.value_of("FQDN").unwrap();
+        let mut body = HashMap::new();
+        body.insert(TRANSFER_PROHIBITED, params.is_present("LOCK"));
+        config
+            .build_req_with_method(Method::PATCH, format!(ROUTE!(), fqdn).as_str())
+            .json(&body)
+    }
+
+    /// Display the operation
+    fn display_human_result(item: Self::Item) {
+        print_operation(&item);
+    }
+
+    /// Create the clap subcommand with its arguments.
+    fn subcommand<'a, 'b>() -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::name())
+            .about("Lock or unlock the transfer of a domain to another registrar")
+            .arg(
+                Arg::with_name("LOCK")
+                    .long("lock")
+                    .help("Prohibit the transfer"),
+            )
+            .arg(
+                Arg::with_name("UNLOCK")
+                    .long("unlock")
+                    .help("Allow the transfer"),
+            )
+            .group(
+                ArgGroup::with_name("STATE")
+                    .args(&["LOCK", "UNLOCK"])
+                    .required(true),
+            );
+        let subcommand = add_fqdn_options(subcommand);
+        add_subcommand_options(subcommand)
+    }
+}
@@ -6,6 +6,9 @@ pub mod dns;
 /// [domain related](https://api.gandi.net/docs/domains/) api
 pub mod domain;
 
+/// Response of the operations
+pub mod operation;
+
 /// [user-info](https://api.gandi.net/docs/organization/#get-v5-organization-user-info) route binding
 pub mod user_info;
 
@@ -0,0 +1,20 @@
+//! Response of the operations that change a resource.
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::super::display::print_info;
+use super::super::formatter::Extra;
+
+/// Acknowledgment of an operation, most of them are processed asynchronously
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Operation {
+    /// message of the API, e.g. `Confirmed`
+    pub message: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Helper to print the operation in the human format
+pub fn print_operation(operation: &Operation) {
+    print_info("message", operation.message.as_str());
+}
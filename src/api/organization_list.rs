@@ -3,6 +3,7 @@ use std::vec::Vec;
 
 use clap::{App, ArgMatches, SubCommand};
 use reqwest::RequestBuilder;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::super::args::pagination::{
@@ -13,12 +14,24 @@ use super::super::args::sharing_id::{
 };
 use super::super::command_handler::GandiSubCommandHandler;
 use super::super::config::Configuration;
-use super::super::display::{add_subcommand_options, print_flag, print_info};
+use super::super::display::{add_subcommand_options, print_empty_line, print_flag, print_info};
+use super::super::formatter::string_enum;
+use super::super::formatter::Extra;
 
 pub const ROUTE: &str = "/v5/organization/organizations";
 
+string_enum! {
+    /// Type of an organization
+    pub enum OrganizationType {
+        Individual => "individual",
+        Company => "company",
+        Association => "association",
+        PublicBody => "publicbody",
+    }
+}
+
 /// Organization Information Format, returned by the API
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Organization {
     /// id of the organizaiton
     id: String,
@@ -26,7 +39,7 @@ pub struct Organization {
     name: String,
     /// type of the organization.
     #[serde(rename(deserialize = "type", serialize = "type"))]
-    type_: String, // Should not be optional
+    type_: OrganizationType, // Should not be optional
 
     /// Flag to indicate the corporate status for the organization.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -52,14 +65,16 @@ pub struct Organization {
     /// VAT number of the (non-individual) organization.
     #[serde(skip_serializing_if = "Option::is_none")]
     vat_number: Option<String>,
+    #[serde(flatten)]
+    extra: Extra,
 }
 
 /// Implement the "show domain" subcommand
 pub struct OrganizationListCommand {}
 
 impl GandiSubCommandHandler for OrganizationListCommand {
-    const COMMAND_GROUP: &'static str = "list";
-    const COMMAND: &'static str = "organizations";
+    const PATH: &'static [&'static str] = &["list", "organizations"];
+    const READ_ONLY: bool = true;
     type Item = Vec<Organization>;
 
     /// Create the route
@@ -74,7 +89,7 @@ impl GandiSubCommandHandler for OrganizationListCommand {
     /// Display the organizaiton main data
     fn display_human_result(organizations: Self::Item) {
         for organization in organizations {
-            println!("");
+            print_empty_line();
             print_info("id", organization.id.as_str());
             print_info("type", organization.type_.as_str());
             print_info("name", organization.name.as_str());
@@ -103,7 +118,7 @@ impl GandiSubCommandHandler for OrganizationListCommand {
 
     /// Create the clap subcommand with its arguments.
     fn subcommand<'a, 'b>() -> App<'a, 'b> {
-        let subcommand = SubCommand::with_name(Self::COMMAND);
+        let subcommand = SubCommand::with_name(Self::name());
         let subcommand = add_pagination_options(subcommand);
         let subcommand = add_sharing_id_options(subcommand);
         add_subcommand_options(subcommand)
@@ -1,17 +1,19 @@
 ///! The [user-info](https://api.gandi.net/docs/organization/#get-v5-organization-user-info) route binding
 use clap::{App, ArgMatches, SubCommand};
 use reqwest::RequestBuilder;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::super::command_handler::GandiSubCommandHandler;
 use super::super::config::Configuration;
-use super::super::display::{add_subcommand_options, print_info};
+use super::super::display::{add_subcommand_options, print_empty_line, print_info, print_text};
+use super::super::formatter::Extra;
 
 /// endpoint of the route.
 const ROUTE: &str = "/v5/organization/user-info";
 
 /// User Information format, returned by the API
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UserInfo {
     /// the sharing id of the user.
     id: String,
@@ -43,6 +45,8 @@ pub struct UserInfo {
     streetaddr2: Option<String>,
     /// zip code of the address.
     zip: Option<String>,
+    #[serde(flatten)]
+    extra: Extra,
 }
 
 /// Implement the "show domain" subcommand
@@ -50,10 +54,9 @@ pub struct UserInfoCommand {}
 
 impl GandiSubCommandHandler for UserInfoCommand {
     type Item = UserInfo;
-    /// CLAP first sub command name.
-    const COMMAND_GROUP: &'static str = "show";
-    /// CLAP second sub command name.
-    const COMMAND: &'static str = "user-info";
+    /// CLAP sub commands names.
+    const PATH: &'static [&'static str] = &["show", "user-info"];
+    const READ_ONLY: bool = true;
 
     /// Create the route
     fn build_req(config: &Configuration, _: &ArgMatches) -> RequestBuilder {
@@ -61,7 +64,8 @@ impl GandiSubCommandHandler for UserInfoCommand {
     }
     /// Display the user info main data
     fn display_human_result(user_info: Self::Item) {
-        println!("{}\n", "User Information");
+        print_text("User Information");
+        print_empty_line();
         print_info("id", user_info.id.as_str());
         print_info("username", user_info.username.as_str());
         print_info("email", user_info.email.as_str());
@@ -70,6 +74,6 @@ impl GandiSubCommandHandler for UserInfoCommand {
 
     /// Create the clap subcommand with its arguments.
     fn subcommand<'a, 'b>() -> App<'a, 'b> {
-        add_subcommand_options(SubCommand::with_name(Self::COMMAND))
+        add_subcommand_options(SubCommand::with_name(Self::name()))
     }
 }
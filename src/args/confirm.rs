@@ -0,0 +1,40 @@
+//! Confirmation of the operations that are charged, or cannot be undone.
+use std::io::{self, BufRead};
+
+use clap::{App, Arg, ArgMatches};
+
+use super::super::display::print_question;
+use super::super::errors::{GandiError, GandiResult};
+
+/// Ask the question on the terminal, unless `--yes` is given.
+///
+/// The question is written on stderr to keep the output of the command
+/// parsable, and it is never asked out of a terminal.
+pub fn confirm(params: &ArgMatches, question: &str) -> GandiResult<()> {
+    if params.is_present("YES") {
+        return Ok(());
+    }
+    if !termion::is_tty(&io::stdin()) {
+        return Err(GandiError::Aborted(format!(
+            "{} Use --yes to confirm out of a terminal",
+            question
+        )));
+    }
+    print_question(question)?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Ok(()),
+        _ => Err(GandiError::Aborted("not confirmed".to_string())),
+    }
+}
+
+/// Create the clap subcommand with its arguments.
+pub fn add_subcommand_options<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("YES")
+            .short("y")
+            .long("yes")
+            .help("Do not ask for a confirmation"),
+    )
+}
@@ -0,0 +1,249 @@
+//! Contacts of a domain, read from YAML or JSON files.
+//!
+//! A file contains one contact, in the format of a role displayed by
+//! `gandi show contacts FQDN --yaml`. The contacts are validated before
+//! being sent.
+use std::collections::HashMap;
+use std::convert::From;
+use std::fs::File;
+use std::sync::Mutex;
+
+use clap::{App, Arg, ArgMatches};
+use lazy_static::lazy_static;
+
+use super::super::api::domain::show_contacts::{Contact, ContactType};
+use super::super::display::{print_warning, Format};
+use super::super::errors::{GandiError, GandiResult};
+
+/// Roles of the contacts of a domain
+pub const ROLES: [&str; 4] = ["owner", "admin", "tech", "bill"];
+
+/// ISO 3166-1 alpha-2 country codes
+const COUNTRIES: [&str; 249] = [
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
+    "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
+    "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE",
+    "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM",
+    "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC",
+    "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG",
+    "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+    "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+/// Check a phone number in the `+CC.NNNN` format, e.g. `+33.123456789`
+fn check_phone(field: &str, phone: &str) -> Result<(), String> {
+    let digits = |val: &str, min: usize, max: usize| {
+        val.len() >= min && val.len() <= max && val.chars().all(|c| c.is_ascii_digit())
+    };
+    match phone
+        .strip_prefix('+')
+        .and_then(|phone| phone.split_once('.'))
+    {
+        Some((code, number)) if digits(code, 1, 3) && digits(number, 4, 14) => Ok(()),
+        _ => Err(format!(
+            "{} {} is not in the +CC.NNNN format, e.g. +33.123456789",
+            field, phone
+        )),
+    }
+}
+
+/// Check the fields of a contact before sending it
+pub fn validate(contact: &Contact) -> Result<(), String> {
+    let mut required = vec![
+        ("given", Some(&contact.given)),
+        ("family", Some(&contact.family)),
+        ("streetaddr", Some(&contact.streetaddr)),
+        ("city", contact.city.as_ref()),
+        ("country", Some(&contact.country)),
+        ("email", Some(&contact.email)),
+    ];
+    if contact.type_ != ContactType::Person {
+        required.push(("orgname", contact.orgname.as_ref()));
+    }
+    for (field, value) in required {
+        if value.filter(|value| !value.trim().is_empty()).is_none() {
+            return Err(format!("{} is required", field));
+        }
+    }
+    if !COUNTRIES.contains(&contact.country.as_str()) {
+        return Err(format!(
+            "country {} is not an ISO 3166 code, e.g. FR",
+            contact.country
+        ));
+    }
+    if !contact.email.contains('@') {
+        return Err(format!("email {} is invalid", contact.email));
+    }
+    let phones = [
+        ("phone", &contact.phone),
+        ("fax", &contact.fax),
+        ("mobile", &contact.mobile),
+    ];
+    for (field, phone) in phones.iter() {
+        if let Some(phone) = phone {
+            check_phone(field, phone)?;
+        }
+    }
+    for (key, value) in contact.extra_parameters.iter().flatten() {
+        let valid_key = key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.');
+        if key.is_empty() || !valid_key {
+            return Err(format!("extra parameter {:?} is not a valid name", key));
+        }
+        if value.trim().is_empty() {
+            return Err(format!("extra parameter {} has no value", key));
+        }
+    }
+    Ok(())
+}
+
+/// Remove the fields set by Gandi, they cannot be sent back, and the
+/// fields unknown to this version, they are never forwarded to the API.
+pub fn writable(mut contact: Contact) -> Contact {
+    contact.same_as_owner = None;
+    contact.reachability = None;
+    contact.validation = None;
+    contact.sharing_id = None;
+    contact.extra.clear();
+    contact
+}
+
+/// Read and validate a contact file, the JSON files are recognized by
+/// their extension.
+pub fn read_contact(path: &str) -> GandiResult<Contact> {
+    let file = File::open(path)?;
+    let contact: Contact = match Format::from_path(path) {
+        Some(Format::JSON) => serde_json::from_reader(file)?,
+        _ => serde_yaml::from_reader(file)?,
+    };
+    validate(&contact).map_err(GandiError::InvalidContact)?;
+    if !contact.extra.is_empty() {
+        let fields: Vec<&str> = contact.extra.keys().map(String::as_str).collect();
+        print_warning(
+            format!("{}: unknown field(s) ignored: {}", path, fields.join(", ")).as_str(),
+        );
+    }
+    Ok(writable(contact))
+}
+
+lazy_static! {
+    /// Contact files parsed by the clap validators, by path: a file is
+    /// read once, even if it changes before the command runs.
+    static ref PARSED: Mutex<HashMap<String, Contact>> = Mutex::new(HashMap::new());
+}
+
+/// Clap validator of a contact file, the parsed contact is kept for `ContactFiles`
+fn parse_contact_arg(path: String) -> Result<(), String> {
+    let contact = read_contact(&path).map_err(|err| format!("{}: {}", path, err))?;
+    PARSED.lock().unwrap().insert(path, contact);
+    Ok(())
+}
+
+/// Contacts given by the command line, by role
+pub struct ContactFiles {
+    pub owner: Option<Contact>,
+    pub admin: Option<Contact>,
+    pub tech: Option<Contact>,
+    pub bill: Option<Contact>,
+}
+
+impl ContactFiles {
+    /// The contact of the role, if given
+    pub fn get(&self, role: &str) -> Option<&Contact> {
+        match role {
+            "owner" => self.owner.as_ref(),
+            "admin" => self.admin.as_ref(),
+            "tech" => self.tech.as_ref(),
+            "bill" => self.bill.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Retrieve the contacts from the clap subcommand arguments
+impl<'a> From<&'a ArgMatches<'a>> for ContactFiles {
+    fn from(params: &ArgMatches<'a>) -> Self {
+        // files are parsed by the clap validators
+        let contact = |name| {
+            params
+                .value_of(name)
+                .and_then(|path| PARSED.lock().unwrap().get(path).cloned())
+        };
+        ContactFiles {
+            owner: contact("OWNER"),
+            admin: contact("ADMIN"),
+            tech: contact("TECH"),
+            bill: contact("BILL"),
+        }
+    }
+}
+
+/// Clap argument of the contact file of a role
+fn contact_arg<'a, 'b>(name: &'a str, role: &'a str, help: &'b str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(role)
+        .takes_value(true)
+        .value_name("FILE")
+        .validator(parse_contact_arg)
+        .help(help)
+}
+
+/// Create the clap subcommand with its arguments, the owner is added,
+/// and required, if `with_owner` is true.
+pub fn add_subcommand_options<'a, 'b>(app: App<'a, 'b>, with_owner: bool) -> App<'a, 'b> {
+    let app = if with_owner {
+        app.arg(contact_arg("OWNER", "owner", "Owner contact file, YAML or JSON").required(true))
+    } else {
+        app
+    };
+    app.arg(contact_arg("ADMIN", "admin", "Administrative contact file"))
+        .arg(contact_arg("TECH", "tech", "Technical contact file"))
+        .arg(contact_arg("BILL", "bill", "Billing contact file"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_phone() {
+        assert!(check_phone("phone", "+33.123456789").is_ok());
+        assert!(check_phone("phone", "+1.5551234").is_ok());
+        assert!(check_phone("phone", "+123.12345678901234").is_ok());
+    }
+
+    #[test]
+    fn test_check_phone_invalid() {
+        for phone in &[
+            "",
+            "33.123456789",
+            "+33123456789",
+            "+.123456789",
+            "+1234.123456789",
+            "+33.123",
+            "+33.123456789012345",
+            "+33.12 34 56 78",
+            "+3a.123456789",
+            "+33.123.456789",
+        ] {
+            assert_eq!(
+                check_phone("phone", phone),
+                Err(format!(
+                    "phone {} is not in the +CC.NNNN format, e.g. +33.123456789",
+                    phone
+                )),
+                "{}",
+                phone
+            );
+        }
+    }
+}
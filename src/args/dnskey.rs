@@ -0,0 +1,145 @@
+//! DNSKEY CLI arguments, given as a record in the zone file syntax or
+//! as its fields.
+use std::convert::From;
+
+use clap::{App, Arg, ArgGroup, ArgMatches};
+
+use super::super::dnssec::{algorithm_name, decode_public_key, PROTOCOL};
+
+/// Fields of a DNSKEY record
+#[derive(Debug)]
+pub struct DnsKeyFields {
+    /// owner of the record, if given
+    pub owner: Option<String>,
+    pub flags: u16,
+    pub algorithm: u8,
+    pub public_key: String,
+}
+
+/// Parse the flags, only the zone keys, 256 (ZSK) and 257 (KSK), are published.
+fn parse_flags(flags: &str) -> Result<u16, String> {
+    match flags.parse::<u16>() {
+        Ok(flags) if flags == 256 || flags == 257 => Ok(flags),
+        _ => Err(format!(
+            "invalid flags {}, expected 256 (ZSK) or 257 (KSK)",
+            flags
+        )),
+    }
+}
+
+/// Parse the number of a DNSSEC algorithm, e.g. 13 for ECDSAP256SHA256
+fn parse_algorithm(algorithm: &str) -> Result<u8, String> {
+    match algorithm.parse::<u8>() {
+        Ok(algorithm) if algorithm_name(algorithm) != "unknown" => Ok(algorithm),
+        _ => Err(format!("unknown DNSSEC algorithm {}", algorithm)),
+    }
+}
+
+/// Parse a DNSKEY record in the zone file syntax, e.g.
+/// `example.org. 3600 IN DNSKEY 257 3 13 mdsswUyr...`. The owner, ttl and
+/// class are optional, comments and parentheses are ignored, so a `.key`
+/// file of `dnssec-keygen` can be given as is.
+pub fn parse_record(record: &str) -> Result<DnsKeyFields, String> {
+    let record = record
+        .lines()
+        .map(|line| line.split(';').next().unwrap_or_default())
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .replace(['(', ')'], " ");
+    let tokens: Vec<&str> = record.split_whitespace().collect();
+    let (owner, rdata) = match tokens
+        .iter()
+        .position(|token| token.eq_ignore_ascii_case("DNSKEY"))
+    {
+        Some(idx) => {
+            let owner = tokens[..idx]
+                .first()
+                .filter(|token| {
+                    !token.chars().all(|c| c.is_ascii_digit()) && !token.eq_ignore_ascii_case("IN")
+                })
+                .map(|owner| owner.to_string());
+            (owner, &tokens[idx + 1..])
+        }
+        None => (None, &tokens[..]),
+    };
+    if rdata.len() < 4 {
+        return Err(format!(
+            "invalid DNSKEY record {}, expected FLAGS PROTOCOL ALGORITHM PUBLIC_KEY",
+            record.trim()
+        ));
+    }
+    if rdata[1] != PROTOCOL.to_string() {
+        return Err(format!(
+            "invalid protocol {}, expected {}",
+            rdata[1], PROTOCOL
+        ));
+    }
+    let public_key = rdata[3..].concat();
+    decode_public_key(&public_key)?;
+    Ok(DnsKeyFields {
+        owner,
+        flags: parse_flags(rdata[0])?,
+        algorithm: parse_algorithm(rdata[2])?,
+        public_key,
+    })
+}
+
+/// Retrieve the key from the clap subcommand arguments
+impl<'a> From<&'a ArgMatches<'a>> for DnsKeyFields {
+    fn from(params: &ArgMatches<'a>) -> Self {
+        // values are checked by the clap validators
+        match params.value_of("RECORD") {
+            Some(record) => parse_record(record).unwrap(),
+            None => DnsKeyFields {
+                owner: None,
+                flags: parse_flags(params.value_of("FLAGS").unwrap()).unwrap(),
+                algorithm: parse_algorithm(params.value_of("ALGORITHM").unwrap()).unwrap(),
+                public_key: params
+                    .value_of("PUBLIC_KEY")
+                    .unwrap()
+                    .split_whitespace()
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// Create the clap subcommand with its arguments.
+pub fn add_subcommand_options<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("RECORD")
+            .long("record")
+            .takes_value(true)
+            .validator(|record| parse_record(&record).map(|_| ()))
+            .help("DNSKEY record in the zone file syntax"),
+    )
+    .arg(
+        Arg::with_name("FLAGS")
+            .long("flags")
+            .takes_value(true)
+            .conflicts_with("RECORD")
+            .validator(|flags| parse_flags(&flags).map(|_| ()))
+            .help("Flags of the key, 256 (ZSK) or 257 (KSK)"),
+    )
+    .arg(
+        Arg::with_name("ALGORITHM")
+            .long("algorithm")
+            .takes_value(true)
+            .conflicts_with("RECORD")
+            .validator(|algorithm| parse_algorithm(&algorithm).map(|_| ()))
+            .help("DNSSEC algorithm number, e.g. 13 for ECDSAP256SHA256"),
+    )
+    .arg(
+        Arg::with_name("PUBLIC_KEY")
+            .long("public-key")
+            .takes_value(true)
+            .requires_all(&["FLAGS", "ALGORITHM"])
+            .validator(|public_key| decode_public_key(&public_key).map(|_| ()))
+            .help("Public key, base64 encoded"),
+    )
+    .group(
+        ArgGroup::with_name("KEY")
+            .args(&["RECORD", "PUBLIC_KEY"])
+            .required(true),
+    )
+}
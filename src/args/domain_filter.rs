@@ -0,0 +1,121 @@
+//! Filters of the domain list, the tag is filtered on the client side.
+use std::convert::From;
+
+use clap::{App, Arg, ArgMatches};
+use reqwest::RequestBuilder;
+
+use super::super::api::domain::list::{Domain, NameServerType};
+
+/// Fields the domain list can be sorted by, prefix with `-` to reverse.
+const SORT_BY_FIELDS: [&str; 8] = [
+    "fqdn",
+    "-fqdn",
+    "tld",
+    "-tld",
+    "created_at",
+    "-created_at",
+    "registry_ends_at",
+    "-registry_ends_at",
+];
+
+/// Filters of the domain list route, forwarded as query parameters.
+///
+/// Every unset field is left out of the request.
+#[derive(Debug, Default)]
+pub struct DomainFilter {
+    /// Fully qualified domain name, wildcards are allowed (`*.shop`).
+    pub fqdn: Option<String>,
+    /// Top level domain, without the leading dot.
+    pub tld: Option<String>,
+    /// Nameserver kind, one of "abc", "livedns" or "other".
+    pub nameserver: Option<String>,
+    /// Organization id of the resellee owning the domains.
+    pub resellee_id: Option<String>,
+    /// Field used to sort the list.
+    pub sort_by: Option<String>,
+    /// Tag of the domains, the route has no such filter.
+    pub tag: Option<String>,
+}
+
+impl DomainFilter {
+    /// Inject the parameters of the cli in the http request
+    pub fn build_req(&self, req: RequestBuilder) -> RequestBuilder {
+        let params = [
+            ("fqdn", &self.fqdn),
+            ("tld", &self.tld),
+            ("nameserver", &self.nameserver),
+            ("resellee_id", &self.resellee_id),
+            ("sort_by", &self.sort_by),
+        ];
+        params.iter().fold(req, |req, (key, val)| match val {
+            Some(val) => req.query(&[(key, val.as_str())]),
+            None => req,
+        })
+    }
+
+    /// The domain has the tag of the filter, if any
+    pub fn matches(&self, domain: &Domain) -> bool {
+        match &self.tag {
+            Some(tag) => domain.tags.iter().flatten().any(|t| t == tag),
+            None => true,
+        }
+    }
+}
+
+/// Retrieve the filters from the clap subcommand arguments
+impl<'a> From<&'a ArgMatches<'a>> for DomainFilter {
+    fn from(params: &ArgMatches<'a>) -> Self {
+        let value = |name: &str| params.value_of(name).map(|val| val.to_string());
+        DomainFilter {
+            fqdn: value("FILTER_FQDN"),
+            tld: value("FILTER_TLD").map(|tld| tld.trim_start_matches('.').to_string()),
+            nameserver: value("FILTER_NAMESERVER"),
+            resellee_id: value("FILTER_RESELLEE_ID"),
+            sort_by: value("SORT_BY"),
+            tag: value("FILTER_TAG"),
+        }
+    }
+}
+
+/// Create the clap subcommand with its arguments.
+pub fn add_subcommand_options<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("FILTER_FQDN")
+            .long("fqdn")
+            .takes_value(true)
+            .help("Filter on the domain name, wildcards allowed (e.g. '*.shop')"),
+    )
+    .arg(
+        Arg::with_name("FILTER_TLD")
+            .long("tld")
+            .takes_value(true)
+            .help("Filter on the top level domain"),
+    )
+    .arg(
+        Arg::with_name("FILTER_NAMESERVER")
+            .long("nameserver")
+            .takes_value(true)
+            .possible_values(NameServerType::VALUES)
+            .help("Filter on the kind of nameservers"),
+    )
+    .arg(
+        Arg::with_name("FILTER_RESELLEE_ID")
+            .long("resellee-id")
+            .takes_value(true)
+            .help("Filter on the resellee organization id"),
+    )
+    .arg(
+        Arg::with_name("FILTER_TAG")
+            .long("tag")
+            .takes_value(true)
+            .help("Filter on a tag, every pages of the list are fetched"),
+    )
+    .arg(
+        Arg::with_name("SORT_BY")
+            .long("sort-by")
+            .takes_value(true)
+            .allow_hyphen_values(true)
+            .possible_values(&SORT_BY_FIELDS)
+            .help("Sort the list, prefix the field with '-' to reverse the order"),
+    )
+}
@@ -0,0 +1,69 @@
+//! Duration of a domain registration or renewal.
+use std::convert::From;
+
+use clap::{App, Arg, ArgMatches};
+
+/// Durations accepted by the registries, in years
+const MAX_YEARS: usize = 10;
+
+/// Parse a number of years, between 1 and 10.
+pub fn parse_years(years: &str) -> Result<usize, String> {
+    match years.parse::<usize>() {
+        Ok(years) if years > 0 && years <= MAX_YEARS => Ok(years),
+        _ => Err(format!(
+            "invalid duration {}, expected 1 to {} years",
+            years, MAX_YEARS
+        )),
+    }
+}
+
+/// Duration parameter
+pub struct DomainDuration {
+    pub years: usize,
+}
+
+/// Retrieve the duration from the clap subcommand arguments
+impl<'a> From<&'a ArgMatches<'a>> for DomainDuration {
+    fn from(params: &ArgMatches<'a>) -> Self {
+        // the value is checked by the clap validator
+        DomainDuration {
+            years: parse_years(params.value_of("DURATION").unwrap()).unwrap(),
+        }
+    }
+}
+
+/// Create the clap subcommand with its arguments.
+pub fn add_subcommand_options<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("DURATION")
+            .short("d")
+            .long("duration")
+            .takes_value(true)
+            .value_name("YEARS")
+            .default_value("1")
+            .validator(|years| parse_years(&years).map(|_| ()))
+            .help("Number of years"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_years() {
+        assert_eq!(parse_years("1"), Ok(1));
+        assert_eq!(parse_years("10"), Ok(10));
+        for years in &["", "0", "11", "-1", "2y", "1.5"] {
+            assert_eq!(
+                parse_years(years),
+                Err(format!(
+                    "invalid duration {}, expected 1 to 10 years",
+                    years
+                )),
+                "{}",
+                years
+            );
+        }
+    }
+}
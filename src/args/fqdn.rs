@@ -2,6 +2,29 @@
 
 use clap::{App, Arg};
 
+/// Check a host name, e.g. `ns1.example.org`, a trailing dot is allowed.
+pub fn check_hostname(name: &str) -> Result<(), String> {
+    let labels: Vec<&str> = name.trim_end_matches('.').split('.').collect();
+    let valid_label = |label: &&str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    if name.len() <= 253 && labels.len() > 1 && labels.iter().all(valid_label) {
+        Ok(())
+    } else {
+        Err(format!("invalid host name {}", name))
+    }
+}
+
+/// Host name in its canonical form to compare it, lowercased and without
+/// trailing dot.
+pub fn normalize_hostname(name: &str) -> String {
+    name.trim_end_matches('.').to_lowercase()
+}
+
 /// Create the clap subcommand with its arguments.
 pub fn add_fqdn_options<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     app.arg(
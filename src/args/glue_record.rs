@@ -0,0 +1,46 @@
+//! Glue record CLI arguments.
+use std::net::IpAddr;
+
+use clap::{App, Arg, ArgMatches};
+
+/// Check an IPv4 or IPv6 address
+fn check_ip(ip: &str) -> Result<(), String> {
+    ip.parse::<IpAddr>()
+        .map(|_| ())
+        .map_err(|_| format!("invalid IP address {}", ip))
+}
+
+/// Addresses of the command line, in their canonical form
+pub fn glue_record_ips(params: &ArgMatches) -> Vec<String> {
+    // checked by the clap validator
+    params
+        .values_of("IPS")
+        .map(|ips| {
+            ips.map(|ip| ip.parse::<IpAddr>().unwrap().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Create the clap subcommand with its arguments, the addresses are
+/// added if `with_ips` is true.
+pub fn add_subcommand_options<'a, 'b>(app: App<'a, 'b>, with_ips: bool) -> App<'a, 'b> {
+    let app = app.arg(
+        Arg::with_name("NAME")
+            .index(2)
+            .required(true)
+            .help("Name of the host, without the domain, e.g. ns1"),
+    );
+    if with_ips {
+        app.arg(
+            Arg::with_name("IPS")
+                .index(3)
+                .multiple(true)
+                .required(true)
+                .validator(|ip| check_ip(&ip))
+                .help("IPv4 and IPv6 addresses of the host"),
+        )
+    } else {
+        app
+    }
+}
@@ -1,6 +1,20 @@
+/// confirmation of the operations
+pub mod confirm;
+/// contact files CLI arguments
+pub mod contacts;
+/// DNSKEY CLI arguments
+pub mod dnskey;
+/// domain list filters CLI arguments
+pub mod domain_filter;
+/// registration duration CLI arguments
+pub mod duration;
 /// domain name argument CLI arguments
 pub mod fqdn;
+/// glue record CLI arguments
+pub mod glue_record;
 /// pagination CLI arguments
 pub mod pagination;
 /// sharing_id parameter
 pub mod sharing_id;
+/// watch mode CLI arguments
+pub mod watch;
@@ -0,0 +1,216 @@
+//! Re-run a read-only command periodically.
+use std::convert::From;
+use std::time::Duration;
+
+use clap::{App, Arg, ArgMatches};
+use serde_json::Value;
+
+/// Operator of an `--until` condition
+#[derive(Debug, PartialEq)]
+enum Operator {
+    /// the field is set, and is not false, zero or empty
+    Truthy,
+    Equal(String),
+    NotEqual(String),
+}
+
+/// Condition on the item that stops the watch, e.g. `dates.registry_ends_at`,
+/// `nameserver.current==livedns` or `0.rrset_type!=CNAME`.
+#[derive(Debug)]
+pub struct Condition {
+    /// dotted path of the field, array items are selected by their index
+    path: Vec<String>,
+    operator: Operator,
+}
+
+impl Condition {
+    /// Parse the condition of the command line
+    pub fn parse(condition: &str) -> Result<Self, String> {
+        let (path, operator) = if let Some(idx) = condition.find("!=") {
+            let value = condition[idx + 2..].to_string();
+            (&condition[..idx], Operator::NotEqual(value))
+        } else if let Some(idx) = condition.find("==") {
+            let value = condition[idx + 2..].to_string();
+            (&condition[..idx], Operator::Equal(value))
+        } else {
+            (condition, Operator::Truthy)
+        };
+        let path = path.trim();
+        if path.is_empty() {
+            return Err(format!("missing field in the condition {}", condition));
+        }
+        Ok(Condition {
+            path: path.split('.').map(str::to_string).collect(),
+            operator,
+        })
+    }
+
+    /// Evaluate the condition on the item, serialized as in the JSON format
+    pub fn holds(&self, item: &Value) -> bool {
+        let field = self.path.iter().try_fold(item, |value, key| match value {
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|idx| items.get(idx)),
+            Value::Object(fields) => fields.get(key),
+            _ => None,
+        });
+        match &self.operator {
+            Operator::Truthy => match field {
+                None | Some(Value::Null) | Some(Value::Bool(false)) => false,
+                Some(Value::String(val)) => !val.is_empty(),
+                Some(Value::Array(val)) => !val.is_empty(),
+                Some(Value::Object(val)) => !val.is_empty(),
+                Some(Value::Number(val)) => val.as_f64() != Some(0.0),
+                Some(Value::Bool(true)) => true,
+            },
+            Operator::Equal(expected) => field.map(as_text).as_ref() == Some(expected),
+            Operator::NotEqual(expected) => field.map(as_text).as_ref() != Some(expected),
+        }
+    }
+}
+
+/// Text of a field, as written in a condition
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::String(val) => val.clone(),
+        Value::Null => "null".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+/// Parse an interval like `30`, `30s`, `5m` or `1h`.
+pub fn parse_interval(interval: &str) -> Result<Duration, String> {
+    let (number, unit) = match interval.char_indices().last() {
+        Some((idx, 's')) => (&interval[..idx], 1),
+        Some((idx, 'm')) => (&interval[..idx], 60),
+        Some((idx, 'h')) => (&interval[..idx], 3600),
+        _ => (interval, 1),
+    };
+    match number
+        .parse::<u64>()
+        .ok()
+        .filter(|number| *number > 0)
+        .and_then(|number| number.checked_mul(unit))
+    {
+        Some(secs) => Ok(Duration::from_secs(secs)),
+        None => Err(format!(
+            "invalid interval {}, expected e.g. 30s or 5m",
+            interval
+        )),
+    }
+}
+
+/// Watch parameters
+pub struct Watch {
+    /// Delay between two runs, no watch if None
+    pub interval: Option<Duration>,
+    /// Stop watching once the condition holds
+    pub until: Option<Condition>,
+}
+
+/// Retrieve the watch parameters from the clap subcommand arguments
+impl<'a> From<&'a ArgMatches<'a>> for Watch {
+    fn from(params: &ArgMatches<'a>) -> Self {
+        // values are checked by the clap validators
+        Watch {
+            interval: params
+                .value_of("WATCH")
+                .map(|interval| parse_interval(interval).unwrap()),
+            until: params
+                .value_of("UNTIL")
+                .map(|condition| Condition::parse(condition).unwrap()),
+        }
+    }
+}
+
+/// Create the clap subcommand with its arguments.
+pub fn add_subcommand_options<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("WATCH")
+            .long("watch")
+            .takes_value(true)
+            .value_name("INTERVAL")
+            .conflicts_with("OUTPUT")
+            .validator(|interval| parse_interval(&interval).map(|_| ()))
+            .help("Run the command every INTERVAL (e.g. 30s, 5m) and highlight the changes"),
+    )
+    .arg(
+        Arg::with_name("UNTIL")
+            .long("until")
+            .takes_value(true)
+            .value_name("CONDITION")
+            .requires("WATCH")
+            .validator(|condition| Condition::parse(&condition).map(|_| ()))
+            .help(
+                "Stop watching when FIELD, FIELD==VALUE or FIELD!=VALUE holds on the JSON output",
+            ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("30"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_interval("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_interval("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_interval("1h"), Ok(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_parse_interval_invalid() {
+        let overflow = format!("{}h", u64::MAX);
+        for interval in &["", "0", "0s", "s", "-5m", "1.5m", "5d", "5 m", &overflow] {
+            assert_eq!(
+                parse_interval(interval),
+                Err(format!(
+                    "invalid interval {}, expected e.g. 30s or 5m",
+                    interval
+                )),
+                "{}",
+                interval
+            );
+        }
+    }
+
+    #[test]
+    fn test_condition_parse() {
+        let condition = Condition::parse("nameserver.current==livedns").unwrap();
+        assert_eq!(condition.path, vec!["nameserver", "current"]);
+        assert_eq!(condition.operator, Operator::Equal("livedns".to_string()));
+        let condition = Condition::parse("0.rrset_type!=CNAME").unwrap();
+        assert_eq!(condition.path, vec!["0", "rrset_type"]);
+        assert_eq!(condition.operator, Operator::NotEqual("CNAME".to_string()));
+        let condition = Condition::parse("dates.registry_ends_at").unwrap();
+        assert_eq!(condition.operator, Operator::Truthy);
+        assert!(Condition::parse("").is_err());
+        assert!(Condition::parse("==livedns").is_err());
+    }
+
+    #[test]
+    fn test_condition_holds() {
+        let item = json!({
+            "nameserver": {"current": "livedns"},
+            "tags": [],
+            "autorenew": false,
+            "authinfo": null,
+            "records": [{"rrset_type": "A", "rrset_ttl": 300}],
+        });
+        let holds = |condition| Condition::parse(condition).unwrap().holds(&item);
+        assert!(holds("nameserver.current==livedns"));
+        assert!(!holds("nameserver.current!=livedns"));
+        assert!(holds("records.0.rrset_type!=CNAME"));
+        assert!(holds("records.0.rrset_ttl==300"));
+        assert!(holds("records.0"));
+        assert!(!holds("records.1"));
+        assert!(!holds("tags"));
+        assert!(!holds("autorenew"));
+        assert!(holds("autorenew==false"));
+        assert!(!holds("missing"));
+        assert!(!holds("missing==null"));
+        assert!(holds("authinfo==null"));
+        assert!(!holds("authinfo"));
+        assert!(holds("missing!=livedns"));
+    }
+}
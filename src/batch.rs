@@ -0,0 +1,297 @@
+//! Run many commands from a script file, or from stdin.
+//!
+//! Every line is parsed with the same clap tree than the command line,
+//! and the commands share the same configuration and http client.
+//! Empty lines and lines starting with `#` are ignored, and the leading
+//! `gandi` of a line is optional.
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::time::Instant;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use serde::Serialize;
+
+use super::browser;
+use super::config::Configuration;
+use super::constants::NAME;
+use super::display;
+use super::errors::{GandiError, GandiResult};
+use super::registry::Registry;
+
+/// Name of the subcommand
+pub const COMMAND: &str = "batch";
+
+/// Outcome of one command of the batch
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Ok,
+    Error,
+    /// Not run because a previous command failed with --fail-fast
+    Skipped,
+}
+
+/// Report of one command of the batch
+#[derive(Debug, Serialize)]
+struct CommandReport {
+    /// line number in the script, starting at 1
+    line: usize,
+    /// the command, as written in the script
+    command: String,
+    status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// time spent running the command
+    duration_ms: u64,
+}
+
+/// Aggregated report of the batch
+#[derive(Debug, Serialize)]
+struct BatchReport {
+    total: usize,
+    succeeded: usize,
+    failed: usize,
+    skipped: usize,
+    commands: Vec<CommandReport>,
+}
+
+/// Create the clap subcommand with its arguments.
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name(COMMAND)
+        .about("Run the commands of a script, one command per line")
+        .arg(
+            Arg::with_name("SCRIPT")
+                .index(1)
+                .required(true)
+                .help("Script file to run, use - to read stdin"),
+        )
+        .arg(
+            Arg::with_name("FAIL_FAST")
+                .long("fail-fast")
+                .help("Stop at the first failing command, instead of continuing"),
+        )
+        .arg(
+            Arg::with_name("REPORT")
+                .long("report")
+                .takes_value(true)
+                .help("Write the JSON report in a file instead of stderr"),
+        )
+}
+
+/// Split a line in arguments like a posix shell does, quotes and
+/// backslashes are supported but there is no expansion.
+fn split_line(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' => {
+                if let Some(arg) = current.take() {
+                    args.push(arg);
+                }
+            }
+            '#' if current.is_none() => break,
+            '\'' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => arg.push(c),
+                        None => return Err("unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) => arg.push(c),
+                            None => return Err("unterminated double quote".to_string()),
+                        },
+                        Some(c) => arg.push(c),
+                        None => return Err("unterminated double quote".to_string()),
+                    }
+                }
+            }
+            '\\' => {
+                if let Some(c) = chars.next() {
+                    current.get_or_insert_with(String::new).push(c);
+                }
+            }
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if let Some(arg) = current.take() {
+        args.push(arg);
+    }
+    Ok(args)
+}
+
+/// Parse and run one command of the batch.
+fn run_command(config: &Configuration, args: Vec<String>) -> GandiResult<()> {
+    let argv = std::iter::once(NAME.to_string()).chain(args);
+    let registry = Registry::new();
+    let matches = super::build_app(&registry)
+        .get_matches_from_safe(argv)
+        .map_err(|err| GandiError::ClapError(err.message))?;
+    if matches.is_present("CONFIG") {
+        return Err(GandiError::ClapError(
+            "--config is not allowed, the configuration is shared by the batch".to_string(),
+        ));
+    }
+    match matches.subcommand_name() {
+        Some(name) if name == COMMAND || name == browser::COMMAND => Err(GandiError::ClapError(
+            format!("{} cannot be run in a batch", name),
+        )),
+        _ => registry.dispatch(config, &matches),
+    }
+}
+
+/// Read the script lines from the file or stdin
+fn read_script(path: &str) -> GandiResult<Vec<String>> {
+    let lines = if path == "-" {
+        io::stdin().lock().lines().collect::<Result<Vec<_>, _>>()?
+    } else {
+        let file = File::open(path)?;
+        BufReader::new(file)
+            .lines()
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    Ok(lines)
+}
+
+/// Run every commands of the script and display the report.
+pub fn run(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+    let script = read_script(params.value_of("SCRIPT").unwrap())?;
+    let fail_fast = params.is_present("FAIL_FAST");
+    // the outputs of the commands follow each others
+    display::set_pager(false);
+
+    let mut commands = Vec::new();
+    let mut stopped = false;
+    for (idx, line) in script.iter().enumerate() {
+        let command = line.trim();
+        let args = match split_line(command) {
+            Ok(ref args) if args.is_empty() => continue,
+            Ok(mut args) => {
+                if args[0] == NAME {
+                    args.remove(0);
+                }
+                Ok(args)
+            }
+            Err(err) => Err(GandiError::ClapError(err)),
+        };
+        let mut report = CommandReport {
+            line: idx + 1,
+            command: command.to_string(),
+            status: Status::Skipped,
+            error: None,
+            duration_ms: 0,
+        };
+        if !stopped {
+            let start = Instant::now();
+            let result = args.and_then(|args| run_command(config, args));
+            report.duration_ms = start.elapsed().as_millis() as u64;
+            match result {
+                Ok(()) => report.status = Status::Ok,
+                Err(err) => {
+                    eprintln!("line {}: {}", report.line, err);
+                    report.status = Status::Error;
+                    report.error = Some(format!("{}", err));
+                    stopped = fail_fast;
+                }
+            }
+        }
+        commands.push(report);
+    }
+
+    let count = |status: Status| commands.iter().filter(|cmd| cmd.status == status).count();
+    let report = BatchReport {
+        total: commands.len(),
+        succeeded: count(Status::Ok),
+        failed: count(Status::Error),
+        skipped: count(Status::Skipped),
+        commands,
+    };
+    let json = serde_json::to_string_pretty(&report)?;
+    match params.value_of("REPORT") {
+        Some(path) => {
+            let mut file = File::create(path)?;
+            writeln!(file, "{}", json)?;
+        }
+        // stdout is left to the output of the commands
+        None => eprintln!("{}", json),
+    }
+
+    if report.failed > 0 {
+        Err(GandiError::BatchError(report.failed, report.total))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(line: &str) -> Vec<String> {
+        split_line(line).unwrap()
+    }
+
+    #[test]
+    fn test_split_line() {
+        assert_eq!(
+            split("domain show example.com"),
+            vec!["domain", "show", "example.com"]
+        );
+        assert_eq!(split("  domain\t show  "), vec!["domain", "show"]);
+        assert!(split("").is_empty());
+        assert!(split("   \t").is_empty());
+    }
+
+    #[test]
+    fn test_split_line_comments() {
+        assert!(split("# a comment").is_empty());
+        assert!(split("   # indented comment").is_empty());
+        assert_eq!(split("domain list # trailing"), vec!["domain", "list"]);
+        assert_eq!(split("tag add a#b"), vec!["tag", "add", "a#b"]);
+    }
+
+    #[test]
+    fn test_split_line_quotes() {
+        assert_eq!(split("tag add 'my tag'"), vec!["tag", "add", "my tag"]);
+        assert_eq!(split("tag add \"my tag\""), vec!["tag", "add", "my tag"]);
+        assert_eq!(split("a 'b \"c\" \\d'"), vec!["a", "b \"c\" \\d"]);
+        assert_eq!(split("a \"b 'c' \\\"d\\\\\""), vec!["a", "b 'c' \"d\\"]);
+        assert_eq!(split("a pre'fix 'suf\"fix\""), vec!["a", "prefix suffix"]);
+        assert_eq!(split("a '' \"\""), vec!["a", "", ""]);
+        assert_eq!(split("a '#b'"), vec!["a", "#b"]);
+    }
+
+    #[test]
+    fn test_split_line_backslashes() {
+        assert_eq!(split("a b\\ c"), vec!["a", "b c"]);
+        assert_eq!(split("a \\'b"), vec!["a", "'b"]);
+        assert_eq!(split("a \\#b"), vec!["a", "#b"]);
+        assert_eq!(split("a b\\"), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_split_line_unterminated() {
+        assert_eq!(
+            split_line("tag add 'my tag"),
+            Err("unterminated single quote".to_string())
+        );
+        assert_eq!(
+            split_line("tag add \"my tag"),
+            Err("unterminated double quote".to_string())
+        );
+        assert_eq!(
+            split_line("tag add \"my tag\\"),
+            Err("unterminated double quote".to_string())
+        );
+    }
+}
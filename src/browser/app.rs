@@ -0,0 +1,229 @@
+//! State of the terminal user interface.
+use std::collections::HashMap;
+
+use tui::widgets::ListState;
+
+use super::super::api::dns::list_records::{DnsRecordsListCommand, Record};
+use super::super::api::dns::list_snapshots::{DnsSnapshotsListCommand, Snapshot};
+use super::super::api::domain::list::{Domain, DomainListCommand};
+use super::super::api::domain::show::{Domain as DomainDetails, DomainShowCommand};
+use super::super::api::domain::show_gluerecords::{DomainGlueRecordsShowCommand, GlueRecord};
+use super::super::args::domain_filter::DomainFilter;
+use super::super::args::sharing_id::SharingSpace;
+use super::super::command_handler::GandiSubCommandHandler;
+use super::super::config::Configuration;
+use super::super::errors::GandiResult;
+
+/// Pane displayed on the right of the domain list
+#[derive(Clone, Copy, PartialEq)]
+pub enum Pane {
+    Domain,
+    LiveDns,
+}
+
+impl Pane {
+    /// Titles of the panes, in the order of their index
+    pub const TITLES: [&'static str; 2] = ["Domain", "LiveDNS"];
+
+    /// Index of the pane in the tabs
+    pub fn index(self) -> usize {
+        match self {
+            Pane::Domain => 0,
+            Pane::LiveDns => 1,
+        }
+    }
+}
+
+/// Informations displayed in the domain pane.
+pub struct DomainPane {
+    pub domain: DomainDetails,
+    /// Glue records are fetched separately and may fail alone.
+    pub glue_records: Result<Vec<GlueRecord>, String>,
+}
+
+/// Informations displayed in the LiveDNS pane.
+pub struct LiveDnsPane {
+    pub records: Vec<Record>,
+    pub snapshots: Vec<Snapshot>,
+}
+
+/// Fetch the item of a command that takes the fqdn as only argument.
+fn fetch_for_fqdn<H: GandiSubCommandHandler>(
+    config: &Configuration,
+    fqdn: &str,
+) -> GandiResult<H::Item> {
+    let params = H::params_from(&[fqdn])?;
+    H::fetch(config, &params)
+}
+
+/// The terminal user interface state.
+pub struct App<'c> {
+    config: &'c Configuration,
+    /// every domains of the account, matching the command line filters
+    pub domains: Vec<Domain>,
+    /// indexes of the domains matching the search
+    pub visible: Vec<usize>,
+    pub list_state: ListState,
+    pub pane: Pane,
+    /// true while the search is being typed
+    pub searching: bool,
+    pub search: String,
+    /// vertical scroll of the right pane
+    pub scroll: u16,
+    /// message displayed in the status bar
+    pub status: String,
+    domain_panes: HashMap<String, Result<DomainPane, String>>,
+    livedns_panes: HashMap<String, Result<LiveDnsPane, String>>,
+}
+
+impl<'c> App<'c> {
+    /// Load the domain list and build the initial state.
+    pub fn new(
+        config: &'c Configuration,
+        filter: &DomainFilter,
+        sharing_space: &SharingSpace,
+    ) -> GandiResult<Self> {
+        let domains = DomainListCommand::fetch_all(config, filter, sharing_space)?;
+        let mut app = App {
+            config,
+            visible: (0..domains.len()).collect(),
+            domains,
+            list_state: ListState::default(),
+            pane: Pane::Domain,
+            searching: false,
+            search: String::new(),
+            scroll: 0,
+            status: String::new(),
+            domain_panes: HashMap::new(),
+            livedns_panes: HashMap::new(),
+        };
+        app.status = format!("{} domains", app.domains.len());
+        if !app.visible.is_empty() {
+            app.list_state.select(Some(0));
+        }
+        Ok(app)
+    }
+
+    /// The domain under the cursor
+    pub fn selected(&self) -> Option<&Domain> {
+        self.list_state
+            .selected()
+            .and_then(|idx| self.visible.get(idx))
+            .map(|idx| &self.domains[*idx])
+    }
+
+    fn selected_fqdn(&self) -> Option<String> {
+        self.selected().map(|domain| domain.fqdn.clone())
+    }
+
+    /// Move the cursor of `offset` domains, staying in the list bounds.
+    pub fn move_cursor(&mut self, offset: isize) {
+        if self.visible.is_empty() {
+            self.list_state.select(None);
+            return;
+        }
+        let last = self.visible.len() as isize - 1;
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let next = current.saturating_add(offset).max(0).min(last);
+        self.list_state.select(Some(next as usize));
+        self.scroll = 0;
+    }
+
+    /// Switch to the other pane
+    pub fn toggle_pane(&mut self) {
+        self.pane = match self.pane {
+            Pane::Domain => Pane::LiveDns,
+            Pane::LiveDns => Pane::Domain,
+        };
+        self.scroll = 0;
+    }
+
+    pub fn scroll_by(&mut self, offset: i32) {
+        self.scroll = (i32::from(self.scroll) + offset).max(0) as u16;
+    }
+
+    /// Update the visible domains with the search, case insensitive.
+    pub fn apply_search(&mut self) {
+        let search = self.search.to_lowercase();
+        let selected = self.selected_fqdn();
+        self.visible = self
+            .domains
+            .iter()
+            .enumerate()
+            .filter(|(_, domain)| domain.fqdn_unicode.to_lowercase().contains(&search))
+            .map(|(idx, _)| idx)
+            .collect();
+        // keep the cursor on the same domain if it is still visible
+        let position = selected.and_then(|fqdn| {
+            self.visible
+                .iter()
+                .position(|idx| self.domains[*idx].fqdn == fqdn)
+        });
+        match (position, self.visible.is_empty()) {
+            (Some(position), _) => self.list_state.select(Some(position)),
+            (None, true) => self.list_state.select(None),
+            (None, false) => self.list_state.select(Some(0)),
+        }
+        self.status = format!("{}/{} domains", self.visible.len(), self.domains.len());
+    }
+
+    /// The domain pane of the selected domain, if already loaded
+    pub fn domain_pane(&self) -> Option<&Result<DomainPane, String>> {
+        self.selected()
+            .and_then(|domain| self.domain_panes.get(&domain.fqdn))
+    }
+
+    /// The LiveDNS pane of the selected domain, if already loaded
+    pub fn livedns_pane(&self) -> Option<&Result<LiveDnsPane, String>> {
+        self.selected()
+            .and_then(|domain| self.livedns_panes.get(&domain.fqdn))
+    }
+
+    /// True if the displayed pane has to be fetched before being drawn
+    pub fn needs_loading(&self) -> bool {
+        match self.pane {
+            Pane::Domain => self.selected().is_some() && self.domain_pane().is_none(),
+            Pane::LiveDns => self.selected().is_some() && self.livedns_pane().is_none(),
+        }
+    }
+
+    /// Fetch the displayed pane of the selected domain, errors are kept
+    /// to be displayed in the pane.
+    pub fn load(&mut self) {
+        let fqdn = match self.selected_fqdn() {
+            Some(fqdn) => fqdn,
+            None => return,
+        };
+        let config = self.config;
+        match self.pane {
+            Pane::Domain => {
+                let pane = fetch_for_fqdn::<DomainShowCommand>(config, &fqdn)
+                    .map(|domain| DomainPane {
+                        domain,
+                        glue_records: fetch_for_fqdn::<DomainGlueRecordsShowCommand>(config, &fqdn)
+                            .map_err(|err| format!("{}", err)),
+                    })
+                    .map_err(|err| format!("{}", err));
+                self.domain_panes.insert(fqdn, pane);
+            }
+            Pane::LiveDns => {
+                let pane = fetch_for_fqdn::<DnsRecordsListCommand>(config, &fqdn)
+                    .and_then(|records| {
+                        let snapshots = fetch_for_fqdn::<DnsSnapshotsListCommand>(config, &fqdn)?;
+                        Ok(LiveDnsPane { records, snapshots })
+                    })
+                    .map_err(|err| format!("{}", err));
+                self.livedns_panes.insert(fqdn, pane);
+            }
+        }
+    }
+
+    /// Forget the loaded panes of the selected domain
+    pub fn refresh(&mut self) {
+        if let Some(fqdn) = self.selected_fqdn() {
+            self.domain_panes.remove(&fqdn);
+            self.livedns_panes.remove(&fqdn);
+            self.status = format!("{} reloaded", fqdn);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Interactive terminal user interface to browse domains, their details
+//! and their LiveDNS records and snapshots.
+//!
+//! The interface reuses the handlers of the `list domains`, `show domain`,
+//! `show glue-records` and `list dns` commands to fetch the data.
+use std::io::{self, stdin};
+use std::sync::mpsc;
+use std::thread;
+
+use clap::{App as ClapApp, ArgMatches, SubCommand};
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+use termion::screen::AlternateScreen;
+use tui::backend::TermionBackend;
+use tui::Terminal;
+
+use super::args::domain_filter::{
+    add_subcommand_options as add_domain_filter_options, DomainFilter,
+};
+use super::args::sharing_id::{add_subcommand_options as add_sharing_id_options, SharingSpace};
+use super::config::Configuration;
+use super::errors::GandiResult;
+
+/// State of the interface
+mod app;
+/// Rendering of the interface
+mod ui;
+
+use app::App;
+
+/// Name of the subcommand
+pub const COMMAND: &str = "tui";
+
+/// Number of lines scrolled by page up and page down
+const PAGE: isize = 10;
+
+/// Create the clap subcommand with its arguments.
+pub fn subcommand<'a, 'b>() -> ClapApp<'a, 'b> {
+    let subcommand = SubCommand::with_name(COMMAND)
+        .about("Browse domains, LiveDNS records and snapshots interactively");
+    let subcommand = add_domain_filter_options(subcommand);
+    add_sharing_id_options(subcommand)
+}
+
+/// Update the state with a key, return false to quit.
+fn handle_key(app: &mut App, key: Key) -> bool {
+    if app.searching {
+        match key {
+            Key::Char('\n') => app.searching = false,
+            Key::Esc => {
+                app.searching = false;
+                app.search.clear();
+                app.apply_search();
+            }
+            Key::Backspace => {
+                app.search.pop();
+                app.apply_search();
+            }
+            Key::Char(c) => {
+                app.search.push(c);
+                app.apply_search();
+            }
+            _ => {}
+        }
+        return true;
+    }
+    match key {
+        Key::Char('q') | Key::Ctrl('c') => return false,
+        Key::Char('j') | Key::Down => app.move_cursor(1),
+        Key::Char('k') | Key::Up => app.move_cursor(-1),
+        Key::Char('g') | Key::Home => app.move_cursor(isize::MIN),
+        Key::Char('G') | Key::End => app.move_cursor(isize::MAX),
+        Key::PageDown => app.scroll_by(PAGE as i32),
+        Key::PageUp => app.scroll_by(-PAGE as i32),
+        Key::Char('\t') | Key::Char('l') | Key::Char('h') => app.toggle_pane(),
+        Key::Char('/') => {
+            app.searching = true;
+        }
+        Key::Esc => {
+            app.search.clear();
+            app.apply_search();
+        }
+        Key::Char('r') => app.refresh(),
+        _ => {}
+    }
+    true
+}
+
+/// Run the interface until the user quit.
+pub fn run(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+    let filter = DomainFilter::from(params);
+    let sharing_space = SharingSpace::from(params);
+    let mut app = App::new(config, &filter, &sharing_space)?;
+
+    let stdout = io::stdout().into_raw_mode()?;
+    let stdout = AlternateScreen::from(stdout);
+    let mut terminal = Terminal::new(TermionBackend::new(stdout))?;
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for key in stdin().keys().flatten() {
+            if tx.send(key).is_err() {
+                return;
+            }
+        }
+    });
+
+    loop {
+        terminal.draw(|f| ui::draw(f, &mut app))?;
+        if app.needs_loading() {
+            // the key pressed meanwhile are consumed before fetching,
+            // to not load every domains while moving the cursor.
+            let mut running = true;
+            while let Ok(key) = rx.try_recv() {
+                running = handle_key(&mut app, key);
+                if !running {
+                    break;
+                }
+            }
+            if !running {
+                break;
+            }
+            if app.needs_loading() {
+                app.load();
+            }
+            continue;
+        }
+        match rx.recv() {
+            Ok(key) => {
+                if !handle_key(&mut app, key) {
+                    break;
+                }
+            }
+            Err(_) => break,
+        }
+    }
+    Ok(())
+}
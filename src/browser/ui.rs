@@ -0,0 +1,272 @@
+//! Rendering of the terminal user interface.
+use chrono::{DateTime, Utc};
+use tui::backend::Backend;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, List, ListItem, Paragraph, Row, Table, Tabs, Wrap};
+use tui::Frame;
+
+use super::super::api::domain::show_contacts::contact_label;
+use super::super::display::{format_date, relative_date};
+use super::app::{App, DomainPane, LiveDnsPane, Pane};
+
+/// Same colors as the human format of the command line
+fn info<'a>(key: &str, val: &str) -> Spans<'a> {
+    Spans::from(vec![
+        Span::styled(format!("{}: ", key), Style::default().fg(Color::LightBlue)),
+        Span::styled(val.to_string(), Style::default().fg(Color::Green)),
+    ])
+}
+
+fn title<'a>(val: &str) -> Spans<'a> {
+    Spans::from(Span::styled(
+        val.to_string(),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))
+}
+
+fn flag(val: bool) -> &'static str {
+    if val {
+        "active"
+    } else {
+        "inactive"
+    }
+}
+
+/// Same rendering of the dates as the human format of the command line
+fn date(val: &Option<DateTime<Utc>>) -> Option<String> {
+    val.as_ref()
+        .map(|date| format!("{} ({})", format_date(date), relative_date(date)))
+}
+
+/// Draw the whole screen
+pub fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+        .split(f.size());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)].as_ref())
+        .split(rows[0]);
+    draw_domains(f, app, columns[0]);
+    draw_details(f, app, columns[1]);
+    draw_status(f, app, rows[1]);
+}
+
+fn draw_domains<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let domains = &app.domains;
+    let items: Vec<ListItem> = app
+        .visible
+        .iter()
+        .map(|idx| {
+            let domain = &domains[*idx];
+            let style = if domain.autorenew {
+                Style::default()
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+            ListItem::new(domain.fqdn_unicode.as_str()).style(style)
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Domains"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, area, &mut app.list_state);
+}
+
+fn draw_details<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+        .split(area);
+    let titles = Pane::TITLES.iter().cloned().map(Spans::from).collect();
+    let tabs = Tabs::new(titles)
+        .block(Block::default().borders(Borders::ALL))
+        .select(app.pane.index())
+        .highlight_style(Style::default().fg(Color::LightBlue));
+    f.render_widget(tabs, rows[0]);
+
+    let block = Block::default().borders(Borders::ALL);
+    let message = |text: &str| Paragraph::new(text.to_string()).block(block.clone());
+    match app.pane {
+        Pane::Domain => match app.domain_pane() {
+            Some(Ok(pane)) => {
+                let paragraph = Paragraph::new(domain_lines(pane))
+                    .block(block.title(pane.domain.fqdn_unicode.as_str()))
+                    .wrap(Wrap { trim: false })
+                    .scroll((app.scroll, 0));
+                f.render_widget(paragraph, rows[1]);
+            }
+            Some(Err(err)) => f.render_widget(message(err), rows[1]),
+            None => f.render_widget(message("Loading..."), rows[1]),
+        },
+        Pane::LiveDns => match app.livedns_pane() {
+            Some(Ok(pane)) => draw_livedns(f, app, pane, rows[1]),
+            Some(Err(err)) => f.render_widget(message(err), rows[1]),
+            None => f.render_widget(message("Loading..."), rows[1]),
+        },
+    }
+}
+
+/// Lines of the domain pane
+fn domain_lines<'a>(pane: &DomainPane) -> Vec<Spans<'a>> {
+    let domain = &pane.domain;
+    let mut lines = vec![
+        info("id", domain.id.as_str()),
+        info("fqdn", domain.fqdn_unicode.as_str()),
+        info("organization", domain.sharing_space.name.as_str()),
+        info("autorenew", flag(domain.autorenew.enabled)),
+    ];
+    if let Some(tags) = &domain.tags {
+        if !tags.is_empty() {
+            lines.push(info("tags", format!("#{}", tags.join(" #")).as_str()));
+        }
+    }
+
+    lines.push(Spans::default());
+    lines.push(title("Dates"));
+    let dates = &domain.dates;
+    let all_dates = [
+        (
+            "registry created at",
+            date(&Some(dates.registry_created_at)),
+        ),
+        ("registry ends at", date(&dates.registry_ends_at)),
+        ("renew begins at", date(&dates.renew_begins_at)),
+        ("updated at", date(&Some(dates.updated_at))),
+        ("hold begins at", date(&dates.hold_begins_at)),
+        ("hold ends at", date(&dates.hold_ends_at)),
+        (
+            "pending delete ends at",
+            date(&dates.pending_delete_ends_at),
+        ),
+        ("restore ends at", date(&dates.restore_ends_at)),
+        ("deletes at", date(&dates.deletes_at)),
+        ("authinfo expires at", date(&dates.authinfo_expires_at)),
+    ];
+    for (key, val) in all_dates.iter() {
+        if let Some(val) = val {
+            lines.push(info(key, val));
+        }
+    }
+
+    lines.push(Spans::default());
+    lines.push(title("Contacts"));
+    let contacts = &domain.contacts;
+    for (key, contact) in [
+        ("owner", &contacts.owner),
+        ("admin", &contacts.admin),
+        ("tech", &contacts.tech),
+        ("bill", &contacts.bill),
+    ]
+    .iter()
+    {
+        lines.push(info(key, contact_label(contact).as_str()));
+    }
+
+    lines.push(Spans::default());
+    lines.push(title("Nameservers"));
+    for nameserver in domain.nameservers.iter().flatten() {
+        lines.push(Spans::from(nameserver.clone()));
+    }
+
+    lines.push(Spans::default());
+    lines.push(title("Glue records"));
+    match &pane.glue_records {
+        Ok(glues) if glues.is_empty() => lines.push(Spans::from("none")),
+        Ok(glues) => {
+            for glue in glues {
+                lines.push(info(
+                    glue.fqdn_unicode.as_str(),
+                    glue.ips.join(", ").as_str(),
+                ));
+            }
+        }
+        Err(err) => lines.push(Spans::from(err.clone())),
+    }
+    lines
+}
+
+fn draw_livedns<B: Backend>(f: &mut Frame<B>, app: &App, pane: &LiveDnsPane, area: Rect) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
+        .split(area);
+    let records: Vec<Row> = pane
+        .records
+        .iter()
+        .flat_map(|record| {
+            record.rrset_values.iter().map(move |val| {
+                Row::new(vec![
+                    record.rrset_name.clone(),
+                    record.rrset_ttl.to_string(),
+                    record.rrset_type.to_string(),
+                    val.clone(),
+                ])
+            })
+        })
+        .skip(app.scroll as usize)
+        .collect();
+    let header_style = Style::default().fg(Color::LightBlue);
+    let table = Table::new(records)
+        .header(Row::new(vec!["name", "ttl", "type", "value"]).style(header_style))
+        .block(Block::default().borders(Borders::ALL).title("Records"))
+        .widths(
+            [
+                Constraint::Percentage(25),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Percentage(60),
+            ]
+            .as_ref(),
+        );
+    f.render_widget(table, rows[0]);
+
+    let snapshots: Vec<Row> = pane
+        .snapshots
+        .iter()
+        .map(|snapshot| {
+            Row::new(vec![
+                format_date(&snapshot.created_at),
+                snapshot.name.clone(),
+                snapshot.id.clone(),
+            ])
+        })
+        .collect();
+    let table = Table::new(snapshots)
+        .header(Row::new(vec!["created at", "name", "id"]).style(header_style))
+        .block(Block::default().borders(Borders::ALL).title("Snapshots"))
+        .widths(
+            [
+                Constraint::Length(32),
+                Constraint::Percentage(30),
+                Constraint::Percentage(40),
+            ]
+            .as_ref(),
+        );
+    f.render_widget(table, rows[1]);
+}
+
+fn draw_status<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let line = if app.searching {
+        Spans::from(vec![
+            Span::styled("/", Style::default().fg(Color::LightBlue)),
+            Span::raw(app.search.clone()),
+        ])
+    } else {
+        Spans::from(vec![
+            Span::styled(
+                "q:quit j/k:move tab:pane /:search r:reload pgup/pgdn:scroll ",
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::raw(app.status.clone()),
+        ])
+    };
+    f.render_widget(Paragraph::new(line), area);
+    if app.searching {
+        f.set_cursor(area.x + 1 + app.search.chars().count() as u16, area.y);
+    }
+}
@@ -2,37 +2,122 @@
 //! Commands are generic to behave the mode possible identically for user.
 //! For instance --json, --yaml and --toml can be used on every commands
 //!
+use std::thread;
+use std::time::Duration;
+
+use chrono::Utc;
 use clap::{App, ArgMatches};
+use colored::*;
 use reqwest::header::HeaderMap;
-use reqwest::RequestBuilder;
+use reqwest::{RequestBuilder, Response};
+use schemars::{schema_for, JsonSchema};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json;
 use serde_yaml;
 use toml;
 
+use super::args::watch::{add_subcommand_options as add_watch_options, Condition, Watch};
 use super::config::Configuration;
-use super::display::Format;
+use super::display::{capture, format_date, paged, print_text, redraw, write_to, Format};
 use super::errors::{GandiError, GandiResult};
+use super::formatter::csv;
+
+/// Send the http request, the response is returned if it succeeded.
+pub fn send(req: RequestBuilder) -> GandiResult<Response> {
+    check_status(req.send()?)
+}
+
+/// The response, if it succeeded, or the error of the API.
+pub fn check_status(mut resp: Response) -> GandiResult<Response> {
+    if resp.status().is_success() {
+        Ok(resp)
+    } else {
+        Err(GandiError::ReqwestResponseError(
+            format!("{}", resp.status()),
+            resp.text().unwrap_or("".to_string()),
+        ))
+    }
+}
+
+/// Remove the null values, recursively, they cannot be written in toml.
+fn without_nulls(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, without_nulls(value)))
+                .collect(),
+        ),
+        serde_json::Value::Array(values) => serde_json::Value::Array(
+            values
+                .into_iter()
+                .filter(|value| !value.is_null())
+                .map(without_nulls)
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+/// Key of the items of a list in the toml output, a document is a table.
+const TOML_ITEMS: &str = "items";
+
+/// Serialize the item in toml.
+///
+/// The extra fields are flattened after the tables, toml::Value reorders
+/// the keys to emit the values first. toml has no null, they are removed.
+fn to_toml<T: Serialize>(item: &T) -> GandiResult<String> {
+    let value = match without_nulls(serde_json::to_value(item)?) {
+        value @ serde_json::Value::Array(_) => {
+            let mut items = serde_json::Map::new();
+            items.insert(TOML_ITEMS.to_string(), value);
+            serde_json::Value::Object(items)
+        }
+        value => value,
+    };
+    Ok(toml::to_string(&toml::Value::try_from(&value)?)?)
+}
 
 /// Implement this trait on subcommand
 pub trait GandiSubCommandHandler
 where
-    Self::Item: Serialize + DeserializeOwned,
+    Self::Item: Serialize + DeserializeOwned + JsonSchema,
 {
-    const COMMAND_GROUP: &'static str;
-    const COMMAND: &'static str;
+    /// Subcommands names from the command line root, e.g. `&["list", "dns", "records"]`
+    const PATH: &'static [&'static str];
+    /// The command does not write, it is false for every command that
+    /// creates, updates or deletes something.
+    const READ_ONLY: bool;
+    /// The command can be re-run with `--watch`, the read-only ones by default
+    const WATCH: bool = Self::READ_ONLY;
     type Item;
 
+    /// Name of the clap subcommand, the last one of the path.
+    fn name() -> &'static str {
+        Self::PATH[Self::PATH.len() - 1]
+    }
+
     /// Create the clap subcommand with its arguments.
     fn subcommand<'a, 'b>() -> App<'a, 'b>;
 
-    /// Take the parameters and extract the subcommand parameters to properly handle the request
-    /// fn can_handle<'a>(matches: &'a ArgMatches) -> Option<&'a ArgMatches<'a>>;
+    /// Create the clap subcommand, `--watch` is added to the watched commands.
+    fn command_subcommand<'a, 'b>() -> App<'a, 'b> {
+        if Self::WATCH {
+            add_watch_options(Self::subcommand())
+        } else {
+            Self::subcommand()
+        }
+    }
 
     /// Build the http request that will be executed
     fn build_req(config: &Configuration, matches: &ArgMatches) -> RequestBuilder;
 
+    /// Run before sending the request, e.g. to confirm an operation that is charged.
+    fn prepare(_: &Configuration, _: &ArgMatches) -> GandiResult<()> {
+        Ok(())
+    }
+
     /// Display to stdout in case there is no format defined
     fn display_human_result(item: Self::Item);
     /// Override it to display extra informations from the response header
@@ -45,15 +130,19 @@ where
         match format {
             Format::JSON => {
                 let resp = serde_json::to_string(&item)?;
-                println!("{}", resp);
+                print_text(&resp);
             }
             Format::YAML => {
                 let resp = serde_yaml::to_string(&item)?;
-                println!("{}", resp);
+                print_text(&resp);
             }
             Format::TOML => {
-                let resp = toml::to_string(&item)?;
-                println!("{}", resp);
+                let resp = to_toml(&item)?;
+                print_text(&resp);
+            }
+            Format::CSV => {
+                let resp = csv::to_string(&item)?;
+                print_text(&resp);
             }
             Format::HUMAN => {
                 Self::display_human_result(item);
@@ -62,45 +151,104 @@ where
         Ok(())
     }
 
-    /// Process the operation in case the matches is processable.
-    fn handle(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
-        if let Some(params) = Self::can_handle(params) {
-            Self::process(config, params)?;
+    /// Display the JSON Schema of the Item, as it is serialized in json.
+    fn display_schema() -> GandiResult<()> {
+        let schema = schema_for!(Self::Item);
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        Ok(())
+    }
+
+    /// Display the item, with the headers in the human format
+    fn display_item(item: Self::Item, headers: &HeaderMap, format: &Format) -> GandiResult<()> {
+        Self::display_result(item, format)?;
+        if *format == Format::HUMAN {
+            Self::display_human_headers(headers)?;
         }
         Ok(())
     }
 
     /// Process the http request and display the result.
     fn process(config: &Configuration, params: &ArgMatches) -> GandiResult<()> {
+        let watch = Watch::from(params);
+        if let Some(interval) = watch.interval {
+            return Self::watch(config, params, interval, watch.until.as_ref());
+        }
+        Self::prepare(config, params)?;
+        let (item, headers) = Self::query(config, params)?;
+        Self::output(item, &headers, params)
+    }
+
+    /// Send the http request and return the item with the response headers.
+    /// Override it to select the items on the client side.
+    fn query(config: &Configuration, params: &ArgMatches) -> GandiResult<(Self::Item, HeaderMap)> {
+        let mut resp = send(Self::build_req(config, params))?;
+        let item: Self::Item = resp.json()?;
+        Ok((item, resp.headers().clone()))
+    }
+
+    /// Display the item in the format of the parameters, in the output file
+    /// if any, or paged for human.
+    fn output(item: Self::Item, headers: &HeaderMap, params: &ArgMatches) -> GandiResult<()> {
         let format = Format::from(params);
-        let req = Self::build_req(config, &params);
-        let mut resp = req.send()?;
-        if resp.status().is_success() {
-            // println!("{}", resp.text().unwrap_or("".to_string()));
-            let item: Self::Item = resp.json()?;
-            Self::display_result(item, &format)?;
-            if format == Format::HUMAN {
-                Self::display_human_headers(resp.headers())?;
-            }
-            Ok(())
-        } else {
-            Err(GandiError::ReqwestResponseError(
-                format!("{}", resp.status()),
-                resp.text().unwrap_or("".to_string()),
-            ))
+        match params.value_of("OUTPUT") {
+            Some(path) => write_to(path, || Self::display_item(item, headers, &format)),
+            None if format == Format::HUMAN => paged(|| Self::display_item(item, headers, &format)),
+            None => Self::display_item(item, headers, &format),
         }
     }
 
-    /// Check if the operation in case the matches is processable.
-    fn can_handle<'a>(matches: &'a ArgMatches) -> Option<&'a ArgMatches<'a>> {
-        if matches.is_present(Self::COMMAND_GROUP) {
-            let subcommand = matches.subcommand_matches(Self::COMMAND_GROUP).unwrap();
-            if subcommand.is_present(Self::COMMAND) {
-                let params = subcommand.subcommand_matches(Self::COMMAND).unwrap();
-                return Some(params);
+    /// Process the http request every `interval` and redraw the result,
+    /// until the condition holds on the item.
+    fn watch(
+        config: &Configuration,
+        params: &ArgMatches,
+        interval: Duration,
+        until: Option<&Condition>,
+    ) -> GandiResult<()> {
+        let format = Format::from(params);
+        let mut previous = Vec::new();
+        loop {
+            let polled = Self::query(config, params).and_then(|(item, headers)| {
+                let value = serde_json::to_value(&item)?;
+                let (result, lines) = capture(|| Self::display_item(item, &headers, &format));
+                result.map(|_| (value, lines))
+            });
+            let title = format!(
+                "Every {}s: {} - {}",
+                interval.as_secs(),
+                Self::PATH.join(" "),
+                format_date(&Utc::now())
+            );
+            match polled {
+                Ok((value, lines)) => {
+                    redraw(&title, &lines, &previous)?;
+                    if until.iter().any(|condition| condition.holds(&value)) {
+                        return Ok(());
+                    }
+                    previous = lines;
+                }
+                // the error may be temporary, e.g. a network failure
+                Err(err) => redraw(&title, &[format!("{}", err).red().to_string()], &[])?,
             }
+            thread::sleep(interval);
         }
-        None
+    }
+
+    /// Process the http request and return the item, without displaying it.
+    fn fetch(config: &Configuration, params: &ArgMatches) -> GandiResult<Self::Item> {
+        let (item, _) = Self::query(config, params)?;
+        Ok(item)
+    }
+
+    /// Parse the arguments of the subcommand, in order to reuse the handler
+    /// out of the command line, e.g. `&["example.org"]`.
+    fn params_from<'a>(args: &[&str]) -> GandiResult<ArgMatches<'a>> {
+        let subcommand = Self::subcommand();
+        let name = subcommand.get_name().to_string();
+        let args = std::iter::once(name.as_str()).chain(args.iter().cloned());
+        subcommand
+            .get_matches_from_safe(args)
+            .map_err(|err| GandiError::ClapError(err.message))
     }
 }
 
@@ -191,3 +339,47 @@ where
 //         Ok(())
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn domain(fqdn: &str) -> serde_json::Value {
+        serde_json::json!({
+            "fqdn": fqdn,
+            "dates": {"created_at": "2019-01-01T00:00:00Z", "deleted_at": null},
+            "nameserver": {"current": "livedns"},
+            "tags": ["a", null],
+            "new_field": {"lang": null, "level": 1},
+            "status": null
+        })
+    }
+
+    #[test]
+    fn test_to_toml_table() {
+        let resp = to_toml(&domain("example.com")).unwrap();
+        let value: toml::Value = toml::from_str(&resp).unwrap();
+        assert_eq!(value["fqdn"].as_str(), Some("example.com"));
+        assert_eq!(value["dates"].get("deleted_at"), None);
+        assert_eq!(value["new_field"]["level"].as_integer(), Some(1));
+        assert_eq!(value["tags"].as_array().unwrap().len(), 1);
+        assert_eq!(value.get("status"), None);
+    }
+
+    #[test]
+    fn test_to_toml_list() {
+        let resp = to_toml(&vec![domain("example.com"), domain("example.net")]).unwrap();
+        let value: toml::Value = toml::from_str(&resp).unwrap();
+        let items = value[TOML_ITEMS].as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1]["fqdn"].as_str(), Some("example.net"));
+        assert_eq!(items[1]["nameserver"]["current"].as_str(), Some("livedns"));
+    }
+
+    #[test]
+    fn test_to_toml_empty_list() {
+        let resp = to_toml(&Vec::<serde_json::Value>::new()).unwrap();
+        let value: toml::Value = toml::from_str(&resp).unwrap();
+        assert_eq!(value[TOML_ITEMS].as_array().map(Vec::len), Some(0));
+    }
+}
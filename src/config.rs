@@ -3,14 +3,16 @@ use std::io::prelude::*;
 //use std::path::PathBuf;
 use std::fs::File;
 
+use chrono::format::{Item, StrftimeItems};
+use chrono_tz::Tz;
 use clap::ArgMatches;
 use reqwest::header;
-use reqwest::RequestBuilder;
+use reqwest::{Client, Method, RequestBuilder};
 use serde::{Deserialize, Serialize};
 use toml;
 
 use super::constants::VERSION;
-use super::errors::GandiResult;
+use super::errors::{GandiError, GandiResult};
 
 // Build a user agent for our http client
 fn user_agent() -> String {
@@ -36,6 +38,15 @@ pub struct Configuration {
     /// If true, then configuration is overridable via environment variable
     #[serde(default = "default_use_env_vars")]
     use_env_vars: bool,
+    /// Timezone of the displayed dates, e.g. `Europe/Paris`, local time if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timezone: Option<String>,
+    /// strftime format of the displayed dates, e.g. `%d/%m/%Y %H:%M`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date_format: Option<String>,
+    /// Http client, shared by the requests to reuse the connections
+    #[serde(skip, default = "Client::new")]
+    client: Client,
 }
 
 impl Default for Configuration {
@@ -44,6 +55,9 @@ impl Default for Configuration {
             apikey: env_var("GANDI_APIKEY").unwrap_or("".to_string()),
             endpoint: env_var("GANDI_API_ENDPOINT").unwrap_or(default_endpoint()),
             use_env_vars: true,
+            timezone: None,
+            date_format: None,
+            client: Client::new(),
         }
     }
 }
@@ -85,6 +99,27 @@ impl Configuration {
         Ok(res)
     }
 
+    /// The timezone of the displayed dates, `None` for the local time.
+    pub fn timezone(&self) -> GandiResult<Option<Tz>> {
+        match self.timezone.as_ref() {
+            Some(name) => name
+                .parse()
+                .map(Some)
+                .map_err(|_| GandiError::ConfigurationError(format!("Unknown timezone {}", name))),
+            None => Ok(None),
+        }
+    }
+
+    /// The strftime format of the displayed dates, if configured.
+    pub fn date_format(&self) -> GandiResult<Option<&str>> {
+        match self.date_format.as_deref() {
+            Some(fmt) if StrftimeItems::new(fmt).any(|item| item == Item::Error) => Err(
+                GandiError::ConfigurationError(format!("Invalid date format {}", fmt)),
+            ),
+            fmt => Ok(fmt),
+        }
+    }
+
     /// Build http headers for our configuration
     fn construct_headers(&self) -> header::HeaderMap {
         let mut headers = header::HeaderMap::new();
@@ -99,10 +134,24 @@ impl Configuration {
         headers
     }
 
+    /// Environment variables exposing the configuration to external subcommands
+    pub fn env_vars(&self) -> Vec<(&'static str, &str)> {
+        vec![
+            ("GANDI_APIKEY", self.apikey()),
+            ("GANDI_API_ENDPOINT", self.endpoint()),
+        ]
+    }
+
     pub fn build_req(&self, route: &str) -> RequestBuilder {
+        self.build_req_with_method(Method::GET, route)
+    }
+
+    /// Build the request of a route that is not read, e.g. `Method::POST`
+    pub fn build_req_with_method(&self, method: Method, route: &str) -> RequestBuilder {
         let url = format!("{}{}", self.endpoint(), route);
-        let client = reqwest::Client::new();
-        client.get(url.as_str()).headers(self.construct_headers())
+        self.client
+            .request(method, url.as_str())
+            .headers(self.construct_headers())
     }
 }
 
@@ -1,7 +1,56 @@
 //! Display options.
+//!
+//! Every line of the human format goes through the helpers of this module,
+//! which take care of the colors, the dates and of the pager.
+use chrono::{DateTime, Duration, Local, Utc};
+use chrono_tz::Tz;
 use clap::{App, Arg, ArgMatches};
 use colored::*;
+use lazy_static::lazy_static;
 use std::convert::From;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use tempfile::Builder;
+
+use super::errors::GandiResult;
+
+/// Pager used when the PAGER environment variable is not set
+const DEFAULT_PAGER: &str = "less";
+/// Options of less, quit if the output fits and keep the colors
+const DEFAULT_LESS: &str = "FRX";
+/// Format of the dates when the configuration does not define one
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %Z";
+/// Prefix of the lines that changed since the previous run of a watch
+const CHANGED_MARK: &str = ">";
+/// Deadlines closer than this number of days are highlighted
+const DEADLINE_WARNING_DAYS: i64 = 30;
+
+/// State of the human output
+struct Output {
+    /// Send the long outputs to the pager
+    pager: bool,
+    /// Lines waiting for the pager, while paging
+    buffer: Option<Vec<String>>,
+    /// Timezone of the dates, the local time if None
+    timezone: Option<Tz>,
+    /// strftime format of the dates
+    date_format: String,
+}
+
+lazy_static! {
+    static ref OUTPUT: Mutex<Output> = Mutex::new(Output {
+        pager: false,
+        buffer: None,
+        timezone: None,
+        date_format: DEFAULT_DATE_FORMAT.to_string(),
+    });
+}
 
 /// Output format
 #[derive(PartialEq)]
@@ -9,9 +58,35 @@ pub enum Format {
     JSON,
     TOML,
     YAML,
+    CSV,
     HUMAN,
 }
 
+impl Format {
+    /// Name of the format, as exposed to external subcommands
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Format::JSON => "json",
+            Format::TOML => "toml",
+            Format::YAML => "yaml",
+            Format::CSV => "csv",
+            Format::HUMAN => "human",
+        }
+    }
+
+    /// Format of a file, from its extension
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = Path::new(path).extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "json" => Some(Format::JSON),
+            "toml" => Some(Format::TOML),
+            "yaml" | "yml" => Some(Format::YAML),
+            "csv" => Some(Format::CSV),
+            _ => None,
+        }
+    }
+}
+
 /// Retrieve the format from the clap subcommand arguments
 impl<'a> From<&'a ArgMatches<'a>> for Format {
     fn from(params: &ArgMatches<'a>) -> Self {
@@ -22,6 +97,10 @@ impl<'a> From<&'a ArgMatches<'a>> for Format {
             format = Format::TOML;
         } else if params.is_present("YAML") {
             format = Format::YAML;
+        } else if params.is_present("CSV") {
+            format = Format::CSV;
+        } else if let Some(path) = params.value_of("OUTPUT") {
+            format = Format::from_path(path).unwrap_or(Format::HUMAN);
         }
         format
     }
@@ -47,27 +126,399 @@ pub fn add_subcommand_options<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
             .conflicts_with("TOML")
             .help("Display result in yaml"),
     )
+    .arg(
+        Arg::with_name("CSV")
+            .long("csv")
+            .conflicts_with_all(&["JSON", "TOML", "YAML"])
+            .help("Display result in csv"),
+    )
+    .arg(
+        Arg::with_name("OUTPUT")
+            .short("o")
+            .long("output")
+            .takes_value(true)
+            .value_name("PATH")
+            .help("Write the result in a file, only if it succeeded. The format is guessed from the extension by default"),
+    )
+}
+
+/// Add the terminal options to the main command.
+pub fn add_global_options<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("COLOR")
+            .long("color")
+            .takes_value(true)
+            .possible_values(&["auto", "always", "never"])
+            .default_value("auto")
+            .global(true)
+            .help("Colorize the human output, auto disable it if NO_COLOR is set or out of a terminal"),
+    )
+    .arg(
+        Arg::with_name("NO_PAGER")
+            .long("no-pager")
+            .global(true)
+            .help("Do not send long human output to the PAGER"),
+    )
+}
+
+/// Value of a global option, given on the command or one of its parents.
+fn global_value<'a>(params: &'a ArgMatches<'a>, name: &str) -> Option<&'a str> {
+    let mut value = None;
+    let mut current = Some(params);
+    while let Some(params) = current {
+        if params.occurrences_of(name) > 0 {
+            value = params.value_of(name);
+        }
+        current = params.subcommand().1;
+    }
+    value
+}
+
+/// Configure the colors and the pager from the main command arguments.
+pub fn setup(params: &ArgMatches) {
+    let is_tty = termion::is_tty(&io::stdout());
+    let color = match global_value(params, "COLOR") {
+        Some("always") => true,
+        Some("never") => false,
+        _ => {
+            is_tty
+                && env::var("NO_COLOR").unwrap_or_default().is_empty()
+                && env::var("TERM").ok().as_deref() != Some("dumb")
+        }
+    };
+    control::set_override(color);
+    set_pager(is_tty && global_value(params, "NO_PAGER").is_none());
+}
+
+/// Enable or disable the pager, e.g. a batch of commands is never paged.
+pub fn set_pager(enabled: bool) {
+    OUTPUT.lock().unwrap().pager = enabled;
+}
+
+/// Set the timezone and the format of the dates, from the configuration.
+pub fn set_date_options(timezone: Option<Tz>, date_format: Option<&str>) {
+    let mut output = OUTPUT.lock().unwrap();
+    output.timezone = timezone;
+    output.date_format = date_format.unwrap_or(DEFAULT_DATE_FORMAT).to_string();
+}
+
+/// Format the date in the configured timezone and format.
+pub fn format_date(date: &DateTime<Utc>) -> String {
+    let output = OUTPUT.lock().unwrap();
+    let format = output.date_format.as_str();
+    match output.timezone {
+        Some(timezone) => date.with_timezone(&timezone).format(format).to_string(),
+        None => date.with_timezone(&Local).format(format).to_string(),
+    }
+}
+
+/// Phrase a number of units, e.g. `1 day` or `3 days`
+fn plural(count: i64, unit: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+/// Describe the distance between the date and now, e.g. `in 23 days`
+/// or `3 hours ago`.
+pub fn relative_date(date: &DateTime<Utc>) -> String {
+    let delta = date.signed_duration_since(Utc::now());
+    let distance = if delta < Duration::zero() {
+        -delta
+    } else {
+        delta
+    };
+    if distance < Duration::minutes(1) {
+        return "just now".to_string();
+    }
+    let phrase = if distance < Duration::hours(1) {
+        plural(distance.num_minutes(), "minute")
+    } else if distance < Duration::days(2) {
+        plural(distance.num_hours(), "hour")
+    } else if distance < Duration::days(60) {
+        plural(distance.num_days(), "day")
+    } else if distance < Duration::days(730) {
+        plural(distance.num_days() / 30, "month")
+    } else {
+        plural(distance.num_days() / 365, "year")
+    };
+    if delta < Duration::zero() {
+        format!("{} ago", phrase)
+    } else {
+        format!("in {}", phrase)
+    }
+}
+
+/// Write a line of the human output, to stdout or to the pager buffer.
+fn emit(line: String) {
+    let mut output = OUTPUT.lock().unwrap();
+    match output.buffer.as_mut() {
+        Some(buffer) => buffer.push(line),
+        None => println!("{}", line),
+    }
+}
+
+/// Run `display` and return the lines of output it produced, instead of
+/// writing them.
+pub fn capture<F>(display: F) -> (GandiResult<()>, Vec<String>)
+where
+    F: FnOnce() -> GandiResult<()>,
+{
+    let previous = OUTPUT.lock().unwrap().buffer.replace(Vec::new());
+    let result = display();
+    let mut output = OUTPUT.lock().unwrap();
+    let lines = output
+        .buffer
+        .take()
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|text| text.split('\n').map(str::to_string).collect::<Vec<_>>())
+        .collect();
+    output.buffer = previous;
+    (result, lines)
+}
+
+/// Run `display` and send the human output it produced to the pager,
+/// if the pager is enabled and the output does not fit in the terminal.
+pub fn paged<F>(display: F) -> GandiResult<()>
+where
+    F: FnOnce() -> GandiResult<()>,
+{
+    if !OUTPUT.lock().unwrap().pager {
+        return display();
+    }
+    let (result, lines) = capture(display);
+    let height = termion::terminal_size()
+        .map(|(_, height)| height as usize)
+        .unwrap_or(usize::MAX);
+    if lines.len() < height {
+        for line in lines {
+            println!("{}", line);
+        }
+    } else {
+        page(&lines)?;
+    }
+    result
+}
+
+/// Run `display` and write the output it produced in the file at `path`.
+///
+/// The output is written in a temporary file, next to the final one, that
+/// replaces it only if `display` succeeded: the file is never left truncated.
+pub fn write_to<F>(path: &str, display: F) -> GandiResult<()>
+where
+    F: FnOnce() -> GandiResult<()>,
+{
+    // the colors are for terminals only
+    let colorize = control::SHOULD_COLORIZE.should_colorize();
+    control::set_override(false);
+    let (result, lines) = capture(display);
+    control::set_override(colorize);
+    result?;
+
+    let path = Path::new(path);
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut builder = Builder::new();
+    // a new file gets the permissions of a shell redirection, within the umask
+    #[cfg(unix)]
+    builder.permissions(fs::Permissions::from_mode(0o666));
+    let mut file = builder.tempfile_in(dir)?;
+    for line in lines {
+        writeln!(file, "{}", line)?;
+    }
+    file.as_file().sync_all()?;
+    // keep the permissions of the replaced file
+    if let Ok(metadata) = fs::metadata(path) {
+        fs::set_permissions(file.path(), metadata.permissions())?;
+    }
+    file.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+/// Clear the terminal and write the output of a watched command under its
+/// title, the lines that differ from the previous run are highlighted.
+pub fn redraw(title: &str, lines: &[String], previous: &[String]) -> GandiResult<()> {
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    if termion::is_tty(&stdout) {
+        write!(
+            stdout,
+            "{}{}",
+            termion::clear::All,
+            termion::cursor::Goto(1, 1)
+        )?;
+    }
+    writeln!(stdout, "{}\n", title.bold())?;
+    for (idx, line) in lines.iter().enumerate() {
+        if !previous.is_empty() && previous.get(idx) != Some(line) {
+            writeln!(stdout, "{} {}", CHANGED_MARK.bright_yellow().bold(), line)?;
+        } else {
+            writeln!(stdout, "  {}", line)?;
+        }
+    }
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Write the lines in the pager, or in stdout if it cannot be started.
+fn page(lines: &[String]) -> GandiResult<()> {
+    let pager = env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+    let mut words = pager.split_whitespace();
+    let mut command = match words.next() {
+        Some(program) => Command::new(program),
+        None => Command::new(DEFAULT_PAGER),
+    };
+    command.args(words).stdin(Stdio::piped());
+    if env::var_os("LESS").is_none() {
+        command.env("LESS", DEFAULT_LESS);
+    }
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(_) => {
+            for line in lines {
+                println!("{}", line);
+            }
+            return Ok(());
+        }
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        for line in lines {
+            // the user may quit the pager before the end
+            if writeln!(stdin, "{}", line).is_err() {
+                break;
+            }
+        }
+    }
+    child.wait()?;
+    Ok(())
+}
+
+/// Helper to print a line without color in the human format
+pub fn print_text(val: &str) {
+    emit(val.to_string());
+}
+
+/// Helper to print an empty line in the human format
+pub fn print_empty_line() {
+    emit(String::new());
 }
 
 /// Helper to simple line with color in the human format
 pub fn print_line(val: &str) {
-    println!("{}", val.green());
+    emit(format!("{}", val.green()));
 }
 
 /// Helper to print line with color in the human format
 pub fn print_info(key: &str, val: &str) {
-    println!("{}: {}", key.bright_blue(), val.green());
+    emit(format!("{}: {}", key.bright_blue(), val.green()));
+}
+
+/// Helper to print rows aligned in columns, below a header
+pub fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = header.iter().map(|title| title.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: Vec<String>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let titles = header.iter().map(|title| title.to_string()).collect();
+    emit(format!("{}", line(titles).bright_blue()));
+    for row in rows {
+        emit(format!("{}", line(row.clone()).green()));
+    }
 }
 
 /// Helper to print line with color in the human format
 pub fn print_flag(key: &str, val: bool) {
     if val {
-        println!("{}: {}", key.bright_blue(), "active".bright_green());
+        emit(format!(
+            "{}: {}",
+            key.bright_blue(),
+            "active".bright_green()
+        ));
     } else {
-        println!("{}: {}", key.bright_blue(), "inactive".red());
+        emit(format!("{}: {}", key.bright_blue(), "inactive".red()));
+    }
+}
+
+/// Helper to print a date, with its distance from now, in the human format
+pub fn print_date(key: &str, date: Option<&DateTime<Utc>>) {
+    if let Some(date) = date {
+        let value = format!("{} ({})", format_date(date), relative_date(date));
+        emit(format!("{}: {}", key.bright_blue(), value.green()));
+    }
+}
+
+/// Helper to print a date that must not be missed, in yellow when it is
+/// near and in red when it is past.
+pub fn print_deadline(key: &str, date: Option<&DateTime<Utc>>) {
+    if let Some(date) = date {
+        let value = format!("{} ({})", format_date(date), relative_date(date));
+        let remaining = date.signed_duration_since(Utc::now());
+        let value = if remaining < Duration::zero() {
+            value.bright_red()
+        } else if remaining < Duration::days(DEADLINE_WARNING_DAYS) {
+            value.yellow()
+        } else {
+            value.green()
+        };
+        emit(format!("{}: {}", key.bright_blue(), value));
     }
 }
 
+/// Helper to print the changes of a list in the human format, the removed
+/// values are prefixed by `-` and the added ones by `+`
+pub fn print_diff(key: &str, before: &[String], after: &[String]) {
+    emit(format!("{}:", key.bright_blue()));
+    for val in before.iter().filter(|val| !after.contains(val)) {
+        emit(format!("  {}", format!("- {}", val).red()));
+    }
+    for val in after {
+        if before.contains(val) {
+            emit(format!("    {}", val.green()));
+        } else {
+            emit(format!("  {}", format!("+ {}", val).bright_green()));
+        }
+    }
+}
+
+/// Helper to print a message on stderr, out of the output of the command
+pub fn print_notice(val: &str) {
+    eprintln!("{}", val.green());
+}
+
+/// Helper to print a key and its value on stderr, out of the output of the command
+pub fn print_notice_info(key: &str, val: &str) {
+    eprintln!("{}: {}", key.bright_blue(), val.green());
+}
+
+/// Helper to ask a yes or no question on stderr, the answer is read by the caller
+pub fn print_question(question: &str) -> GandiResult<()> {
+    eprint!("{} {} ", question.bold(), "[y/N]".bright_blue());
+    io::stderr().flush()?;
+    Ok(())
+}
+
+/// Helper to print a warning on stderr, out of the output of the command
+pub fn print_warning(val: &str) {
+    eprintln!("{} {}", "warning:".yellow().bold(), val);
+}
+
 /// Helper to print lists in the human format
 pub fn print_list(name: &str, tags: &Option<Vec<String>>) {
     tags.as_ref().map(|tags| {
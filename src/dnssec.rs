@@ -0,0 +1,141 @@
+//! DNSSEC computations on the DNSKEY records, as published by the
+//! registry: the key tag and the DS digests.
+use ring::digest::{digest, Algorithm, SHA1_FOR_LEGACY_USE_ONLY, SHA256, SHA384};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Protocol of every DNSKEY records
+pub const PROTOCOL: u8 = 3;
+
+/// Delegation Signer digest of a DNSKEY
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Ds {
+    /// 1 for SHA-1, 2 for SHA-256, 4 for SHA-384
+    pub digest_type: u8,
+    /// hexadecimal digest, uppercased
+    pub digest: String,
+}
+
+/// Name of the DNSSEC algorithm, from the IANA registry
+pub fn algorithm_name(algorithm: u8) -> &'static str {
+    match algorithm {
+        1 => "RSAMD5",
+        3 => "DSA",
+        5 => "RSASHA1",
+        6 => "DSA-NSEC3-SHA1",
+        7 => "RSASHA1-NSEC3-SHA1",
+        8 => "RSASHA256",
+        10 => "RSASHA512",
+        12 => "ECC-GOST",
+        13 => "ECDSAP256SHA256",
+        14 => "ECDSAP384SHA384",
+        15 => "ED25519",
+        16 => "ED448",
+        _ => "unknown",
+    }
+}
+
+/// Name of the DS digest type
+pub fn digest_type_name(digest_type: u8) -> &'static str {
+    match digest_type {
+        1 => "SHA-1",
+        2 => "SHA-256",
+        4 => "SHA-384",
+        _ => "unknown",
+    }
+}
+
+/// Decode the base64 public key, whitespaces are ignored
+pub fn decode_public_key(public_key: &str) -> Result<Vec<u8>, String> {
+    let public_key: String = public_key.split_whitespace().collect();
+    base64::decode(&public_key).map_err(|err| format!("invalid public key: {}", err))
+}
+
+/// RDATA of the DNSKEY record, in the wire format
+fn rdata(flags: u16, algorithm: u8, public_key: &[u8]) -> Vec<u8> {
+    let mut rdata = Vec::with_capacity(public_key.len() + 4);
+    rdata.extend_from_slice(&flags.to_be_bytes());
+    rdata.push(PROTOCOL);
+    rdata.push(algorithm);
+    rdata.extend_from_slice(public_key);
+    rdata
+}
+
+/// Domain name in the canonical wire format, lowercased
+fn owner_name(fqdn: &str) -> Vec<u8> {
+    let mut name = Vec::with_capacity(fqdn.len() + 2);
+    for label in fqdn
+        .trim_end_matches('.')
+        .split('.')
+        .filter(|label| !label.is_empty())
+    {
+        name.push(label.len() as u8);
+        name.extend(label.bytes().map(|c| c.to_ascii_lowercase()));
+    }
+    name.push(0);
+    name
+}
+
+/// Key tag of the DNSKEY, see RFC 4034 Appendix B
+pub fn key_tag(flags: u16, algorithm: u8, public_key: &[u8]) -> u16 {
+    let rdata = rdata(flags, algorithm, public_key);
+    if algorithm == 1 {
+        // RSAMD5 uses the bits of the modulus
+        let len = rdata.len();
+        return u16::from_be_bytes([rdata[len - 3], rdata[len - 2]]);
+    }
+    let mut acc: u32 = 0;
+    for (idx, byte) in rdata.iter().enumerate() {
+        acc += if idx & 1 == 1 {
+            *byte as u32
+        } else {
+            (*byte as u32) << 8
+        };
+    }
+    acc += (acc >> 16) & 0xFFFF;
+    (acc & 0xFFFF) as u16
+}
+
+/// DS digests of the DNSKEY of the domain, for every digest types
+pub fn ds_digests(fqdn: &str, flags: u16, algorithm: u8, public_key: &[u8]) -> Vec<Ds> {
+    let mut data = owner_name(fqdn);
+    data.extend(rdata(flags, algorithm, public_key));
+    let digests: [(u8, &Algorithm); 3] =
+        [(1, &SHA1_FOR_LEGACY_USE_ONLY), (2, &SHA256), (4, &SHA384)];
+    digests
+        .iter()
+        .map(|(digest_type, algorithm)| Ds {
+            digest_type: *digest_type,
+            digest: digest(algorithm, &data)
+                .as_ref()
+                .iter()
+                .map(|byte| format!("{:02X}", byte))
+                .collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// DNSKEY of the example of RFC 4034, section 5.4
+    const PUBLIC_KEY: &str = "AQOeiiR0GOMYkDshWoSKz9XzfwJr1AYtsmx3TGkJaNXVbfi/2pHm822aJ5iI9BMzNXxeYCmZDRD99WYwYqUSdjMmmAphXdvxegXd/M5+X7OrzKBaMbCVdFLUUh6DhweJBjEVv5f2wwjM9XzcnOf+EPbtG9DMBmADjFDc2w/rljwvFw==";
+
+    #[test]
+    fn test_key_tag() {
+        let public_key = decode_public_key(PUBLIC_KEY).unwrap();
+        assert_eq!(key_tag(256, 5, &public_key), 60485);
+    }
+
+    #[test]
+    fn test_ds_digests() {
+        let public_key = decode_public_key(PUBLIC_KEY).unwrap();
+        let ds = ds_digests("dskey.example.com", 256, 5, &public_key);
+        assert_eq!(ds[0].digest_type, 1);
+        assert_eq!(ds[0].digest, "2BB183AF5F22588179A53B0A98631FAD1A292118");
+        // the trailing dot of the owner name is optional
+        let dotted = ds_digests("dskey.example.com.", 256, 5, &public_key);
+        assert_eq!(dotted[0].digest, ds[0].digest);
+    }
+}
@@ -21,6 +21,17 @@ pub enum GandiError {
     TomlDeError(String),
     TomlSerError(String),
     ReqwestResponseError(String, String),
+    ClapError(String),
+    ConfigurationError(String),
+    BatchError(usize, usize),
+    BulkError(usize, usize),
+    Aborted(String),
+    Unavailable(String, String),
+    InvalidContact(String),
+    TransferFailed(String, String),
+    // External subcommands
+    ExternalCommandNotFound(String, String),
+    ExternalCommandError(String, i32),
 }
 
 /// Result used by method that can failed.
@@ -38,11 +49,44 @@ impl Display for GandiError {
             GandiError::ReqwestResponseError(status, err) => {
                 format!("Request Error {}: {}", status, err)
             }
+            GandiError::ClapError(err) => err.to_string(),
+            GandiError::ConfigurationError(err) => format!("Invalid Configuration: {}", err),
+            GandiError::Aborted(reason) => format!("Aborted: {}", reason),
+            GandiError::Unavailable(name, reason) => {
+                format!("{} is not available: {}", name, reason)
+            }
+            GandiError::InvalidContact(reason) => format!("Invalid contact: {}", reason),
+            GandiError::TransferFailed(name, reason) => {
+                format!("Transfer of {} failed: {}", name, reason)
+            }
+            GandiError::BatchError(failed, total) => {
+                format!("{} of {} commands failed", failed, total)
+            }
+            GandiError::BulkError(failed, total) => {
+                format!("{} of {} domains failed", failed, total)
+            }
+            GandiError::ExternalCommandNotFound(name, program) => format!(
+                "Unknown command {}: no {} executable found in the PATH",
+                name, program
+            ),
+            GandiError::ExternalCommandError(program, code) => {
+                format!("{} exited with status {}", program, code)
+            }
         };
         write!(f, "{}", description)
     }
 }
 
+impl GandiError {
+    /// Exit code of the process when the command failed with this error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            GandiError::ExternalCommandError(_, code) => *code,
+            _ => 1,
+        }
+    }
+}
+
 impl Error for GandiError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         let err: Option<&(dyn Error + 'static)> = match self {
@@ -0,0 +1,72 @@
+//! External subcommands, git style.
+//!
+//! `gandi <name> [args...]` runs the `gandi-<name>` executable found in the
+//! PATH when `<name>` is not a builtin command. The resolved configuration is
+//! exposed to the plugin through environment variables, so it does not have
+//! to load the configuration by itself.
+use std::io::ErrorKind;
+use std::process::Command;
+
+use clap::ArgMatches;
+
+use super::config::Configuration;
+use super::constants::NAME;
+use super::display::Format;
+use super::errors::{GandiError, GandiResult};
+
+/// Environment variable containing the configuration file used, if any.
+pub const ENV_CONFIG: &str = "GANDI_CONFIG";
+/// Environment variable containing the output format requested.
+pub const ENV_FORMAT: &str = "GANDI_OUTPUT_FORMAT";
+
+/// Retrieve the output format from the raw arguments of the plugin
+fn format_from_args(args: &[&str]) -> Format {
+    if args.contains(&"--json") {
+        Format::JSON
+    } else if args.contains(&"--toml") {
+        Format::TOML
+    } else if args.contains(&"--yaml") {
+        Format::YAML
+    } else if args.contains(&"--csv") {
+        Format::CSV
+    } else {
+        Format::HUMAN
+    }
+}
+
+/// Run the `gandi-<name>` executable with the given arguments.
+pub fn run(
+    config: &Configuration,
+    config_path: Option<&str>,
+    name: &str,
+    params: &ArgMatches,
+) -> GandiResult<()> {
+    let args: Vec<&str> = params
+        .values_of("")
+        .map(|vals| vals.collect())
+        .unwrap_or_default();
+    let program = format!("{}-{}", NAME, name);
+    let mut command = Command::new(program.as_str());
+    command
+        .args(&args)
+        .envs(config.env_vars())
+        .env(ENV_FORMAT, format_from_args(&args).as_str());
+    if let Some(path) = config_path {
+        command.env(ENV_CONFIG, path);
+    }
+    let status = command.status().map_err(|err| match err.kind() {
+        ErrorKind::NotFound => {
+            GandiError::ExternalCommandNotFound(name.to_string(), program.clone())
+        }
+        _ => GandiError::IOError(err),
+    })?;
+    if status.success() {
+        Ok(())
+    } else {
+        // killed by a signal if there is no exit code
+        Err(GandiError::ExternalCommandError(
+            program,
+            status.code().unwrap_or(1),
+        ))
+    }
+}
@@ -0,0 +1,134 @@
+//! CSV rendering of the items, from their JSON serialization.
+//!
+//! A list is rendered as one row per element, any other item as a single
+//! row. Nested objects are flattened in columns named by their dotted path,
+//! lists of values are joined by commas and lists of objects are kept in
+//! JSON.
+use serde::Serialize;
+use serde_json::{self, Map, Value};
+
+use super::super::errors::GandiResult;
+
+/// Flatten the object in the columns, the new columns are added at the end.
+fn flatten(prefix: &str, value: &Value, columns: &mut Vec<String>, row: &mut Map<String, Value>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                let column = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&column, field, columns, row);
+            }
+        }
+        _ => {
+            let column = if prefix.is_empty() { "value" } else { prefix };
+            if !columns.iter().any(|known| known == column) {
+                columns.push(column.to_string());
+            }
+            row.insert(column.to_string(), value.clone());
+        }
+    }
+}
+
+/// Text of a cell
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(val)) => val.clone(),
+        Some(Value::Array(items)) if items.iter().all(|item| !item.is_object()) => items
+            .iter()
+            .map(|item| cell(Some(item)))
+            .collect::<Vec<_>>()
+            .join(","),
+        Some(val) => val.to_string(),
+    }
+}
+
+/// Quote the field if it contains a separator, a quote or a new line
+fn escape(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Render the item in CSV, with a header line.
+pub fn to_string<T: Serialize>(item: &T) -> GandiResult<String> {
+    let value = serde_json::to_value(item)?;
+    let items = match value {
+        Value::Array(items) => items,
+        value => vec![value],
+    };
+    let mut columns = Vec::new();
+    let rows: Vec<Map<String, Value>> = items
+        .iter()
+        .map(|item| {
+            let mut row = Map::new();
+            flatten("", item, &mut columns, &mut row);
+            row
+        })
+        .collect();
+
+    let mut lines = vec![columns
+        .iter()
+        .map(|column| escape(column))
+        .collect::<Vec<_>>()
+        .join(",")];
+    for row in rows {
+        let line = columns
+            .iter()
+            .map(|column| escape(&cell(row.get(column))))
+            .collect::<Vec<_>>()
+            .join(",");
+        lines.push(line);
+    }
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape(""), "");
+        assert_eq!(escape("a,b"), "\"a,b\"");
+        assert_eq!(escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape("two\nlines"), "\"two\nlines\"");
+        assert_eq!(escape("cr\r"), "\"cr\r\"");
+    }
+
+    #[test]
+    fn test_to_string_list() {
+        let items = json!([
+            {"fqdn": "example.com", "dates": {"created_at": "2019"}, "tags": ["a", "b"]},
+            {"fqdn": "example.net", "status": null, "extra": "x,y"},
+        ]);
+        assert_eq!(
+            to_string(&items).unwrap(),
+            "dates.created_at,fqdn,tags,extra,status\n\
+             2019,example.com,\"a,b\",,\n\
+             ,example.net,,\"x,y\","
+        );
+    }
+
+    #[test]
+    fn test_to_string_item() {
+        let item = json!({"name": "a \"quoted\" name", "hosts": [{"ip": "1.2.3.4"}], "count": 2});
+        assert_eq!(
+            to_string(&item).unwrap(),
+            "count,hosts,name\n2,\"[{\"\"ip\"\":\"\"1.2.3.4\"\"}]\",\"a \"\"quoted\"\" name\""
+        );
+    }
+
+    #[test]
+    fn test_to_string_values() {
+        assert_eq!(to_string(&json!(["a", "b"])).unwrap(), "value\na\nb");
+        assert_eq!(to_string(&json!([])).unwrap(), "");
+    }
+}
@@ -1,38 +1,199 @@
-//! Stolen here:
-//! https://serde.rs/custom-date-format.html
+//! Serde codec of the dates returned by the API.
+//!
+//! The API is not consistent on the date format, dates are parsed as
+//! RFC 3339 / ISO 8601 with either a `T` or a space as separator, with or
+//! without fractional seconds, and with a `Z`, an offset, or no timezone
+//! at all, which means UTC.
+//!
+//! Dates are serialized in RFC 3339 in UTC, keeping the fractional seconds.
+//!
+//! Use `#[serde(with = "date_formatter")]` for required dates and
+//! `#[serde(default, with = "date_formatter::optional")]` for missing or
+//! null dates.
 
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject, StringValidation};
 use serde::{self, Deserialize, Deserializer, Serializer};
 
-const FORMAT: &'static str = "%Y-%m-%d %H:%M:%S";
+/// Format tried after RFC 3339, for dates with an offset without colon
+const FORMAT_WITH_OFFSET: &str = "%Y-%m-%dT%H:%M:%S%.f%z";
+/// Format tried at last, for dates without timezone
+const FORMAT_WITHOUT_OFFSET: &str = "%Y-%m-%dT%H:%M:%S%.f";
+/// Regular expression matching the serialized dates, exposed in the JSON Schema
+const PATTERN: &str = r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?Z$";
+
+/// Parse a date in any of the formats emitted by the API.
+pub fn parse(s: &str) -> Result<DateTime<Utc>, String> {
+    let s = s.trim();
+    // the date and the time may be separated by a space
+    let normalized = match s.char_indices().nth(10) {
+        Some((idx, ' ')) => format!("{}T{}", &s[..idx], &s[idx + 1..]),
+        _ => s.to_string(),
+    };
+    DateTime::parse_from_rfc3339(&normalized)
+        .or_else(|_| DateTime::<FixedOffset>::parse_from_str(&normalized, FORMAT_WITH_OFFSET))
+        .map(|date| date.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(&normalized, FORMAT_WITHOUT_OFFSET)
+                .map(|date| Utc.from_utc_datetime(&date))
+        })
+        .map_err(|_| format!("invalid date {:?}, expected an ISO 8601 date", s))
+}
+
+/// Format the date in RFC 3339, in UTC, without losing the fractional seconds.
+pub fn format(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
 
-// The signature of a serialize_with function must follow the pattern:
-//
-//    fn serialize<S>(&T, S) -> Result<S::Ok, S::Error>
-//    where
-//        S: Serializer
-//
-// although it may also be generic over the input types T.
 pub fn serialize<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    let s = format!("{}", date.format(FORMAT));
-    serializer.serialize_str(&s)
+    serializer.serialize_str(&format(date))
 }
 
-// The signature of a deserialize_with function must follow the pattern:
-//
-//    fn deserialize<'de, D>(D) -> Result<T, D::Error>
-//    where
-//        D: Deserializer<'de>
-//
-// although it may also be generic over the output types T.
 pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    Utc.datetime_from_str(&s, FORMAT)
-        .map_err(serde::de::Error::custom)
+    parse(&s).map_err(serde::de::Error::custom)
+}
+
+/// String schema of the serialized date
+fn string_schema(instance_type: Vec<InstanceType>) -> Schema {
+    SchemaObject {
+        instance_type: Some(instance_type.into()),
+        format: Some("date-time".to_string()),
+        string: Some(Box::new(StringValidation {
+            pattern: Some(PATTERN.to_string()),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+/// JSON Schema of the serialized date, used by `#[schemars(schema_with)]`
+pub fn schema(_: &mut SchemaGenerator) -> Schema {
+    string_schema(vec![InstanceType::String])
+}
+
+/// Codec of the optional dates, a missing field requires `#[serde(default)]`
+/// and an explicit `null` is deserialized as `None`.
+pub mod optional {
+    use chrono::{DateTime, Utc};
+    use schemars::gen::SchemaGenerator;
+    use schemars::schema::{InstanceType, Schema};
+    use serde::{self, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(date: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match date {
+            Some(date) => serializer.serialize_str(&super::format(date)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(s) => super::parse(&s).map(Some).map_err(serde::de::Error::custom),
+            None => Ok(None),
+        }
+    }
+
+    /// JSON Schema of the serialized date, used by `#[schemars(schema_with)]`
+    pub fn schema(_: &mut SchemaGenerator) -> Schema {
+        super::string_schema(vec![InstanceType::String, InstanceType::Null])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Dates {
+        #[serde(with = "super")]
+        required: DateTime<Utc>,
+        #[serde(default, with = "optional")]
+        optional: Option<DateTime<Utc>>,
+    }
+
+    #[test]
+    fn test_parse() {
+        let expected = Utc.ymd(2019, 1, 2).and_hms(3, 4, 5);
+        let dates = [
+            "2019-01-02T03:04:05Z",
+            "2019-01-02 03:04:05Z",
+            "2019-01-02T03:04:05+00:00",
+            "2019-01-02T05:04:05+02:00",
+            "2019-01-02T05:04:05+0200",
+            "2019-01-01T22:04:05-05:00",
+            "2019-01-02T03:04:05",
+            "2019-01-02 03:04:05",
+            " 2019-01-02T03:04:05Z ",
+        ];
+        for date in dates.iter() {
+            assert_eq!(parse(date), Ok(expected), "{}", date);
+        }
+    }
+
+    #[test]
+    fn test_parse_fractional_seconds() {
+        let date = parse("2019-01-02T03:04:05.123+00:00").unwrap();
+        assert_eq!(date.timestamp_subsec_millis(), 123);
+        let date = parse("2019-01-02 03:04:05.123456").unwrap();
+        assert_eq!(date.timestamp_subsec_micros(), 123456);
+        assert_eq!(format(&date), "2019-01-02T03:04:05.123456Z");
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for date in ["", "2019-01-02", "2019-13-02T03:04:05Z", "yesterday"].iter() {
+            assert!(parse(date).is_err(), "{}", date);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let date = Utc.ymd(2019, 1, 2).and_hms_nano(3, 4, 5, 123_456_789);
+        assert_eq!(parse(&format(&date)), Ok(date));
+    }
+
+    #[test]
+    fn test_format() {
+        let date = parse("2019-01-02T05:04:05+02:00").unwrap();
+        assert_eq!(format(&date), "2019-01-02T03:04:05Z");
+    }
+
+    #[test]
+    fn test_optional() {
+        let dates: Dates =
+            serde_json::from_str(r#"{"required": "2019-01-02 03:04:05", "optional": null}"#)
+                .unwrap();
+        assert_eq!(dates.optional, None);
+        let dates: Dates = serde_json::from_str(r#"{"required": "2019-01-02T03:04:05Z"}"#).unwrap();
+        assert_eq!(dates.optional, None);
+        let dates: Dates = serde_json::from_str(
+            r#"{"required": "2019-01-02T03:04:05Z", "optional": "2019-01-02T03:04:05.5Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_string(&dates).unwrap(),
+            r#"{"required":"2019-01-02T03:04:05Z","optional":"2019-01-02T03:04:05.500Z"}"#
+        );
+        assert!(serde_json::from_str::<Dates>(r#"{"required": null}"#).is_err());
+        assert!(serde_json::from_str::<Dates>(
+            r#"{"required": "2019-01-02T03:04:05Z", "optional": "soon"}"#
+        )
+        .is_err());
+    }
 }
@@ -1,3 +1,11 @@
+pub mod csv;
 pub mod date_formatter;
-pub mod date_formatter_z;
-pub mod optional_date_formatter_z;
+pub mod string_enum;
+
+pub(crate) use string_enum::string_enum;
+
+/// Fields of a response that are not declared in its struct.
+///
+/// Flattened in the structs with `#[serde(flatten)]`, they are re-emitted
+/// in the structured output formats, so nothing the API adds is dropped.
+pub type Extra = std::collections::BTreeMap<String, serde_json::Value>;
@@ -1,43 +0,0 @@
-//! Stolen here:
-//! https://serde.rs/custom-date-format.html
-
-use chrono::{DateTime, TimeZone, Utc};
-use serde::{self, Deserialize, Deserializer, Serializer};
-
-const FORMAT: &'static str = "%Y-%m-%dT%H:%M:%SZ";
-
-// The signature of a serialize_with function must follow the pattern:
-//
-//    fn serialize<S>(&T, S) -> Result<S::Ok, S::Error>
-//    where
-//        S: Serializer
-//
-// although it may also be generic over the input types T.
-pub fn serialize<S>(date: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
-where
-    S: Serializer,
-{
-    if let Some(date) = date {
-        let s = format!("{}", date.format(FORMAT));
-        serializer.serialize_str(&s)
-    } else {
-        serializer.serialize_none()
-    }
-}
-
-// The signature of a deserialize_with function must follow the pattern:
-//
-//    fn deserialize<'de, D>(D) -> Result<T, D::Error>
-//    where
-//        D: Deserializer<'de>
-//
-// although it may also be generic over the output types T.
-pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let s = String::deserialize(deserializer)?;
-    Utc.datetime_from_str(&s, FORMAT)
-        .map(|d| Some(d))
-        .map_err(serde::de::Error::custom)
-}
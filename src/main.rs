@@ -1,6 +1,6 @@
 //! # Alternative Gandi ClI in rust
 
-use clap::{App, Arg, SubCommand};
+use clap::{App, AppSettings, Arg};
 use log::debug;
 use pretty_env_logger;
 
@@ -8,6 +8,10 @@ use pretty_env_logger;
 mod api;
 /// Common params in the CLI that are repetitive
 mod args;
+/// Run many commands from a script
+mod batch;
+/// Interactive terminal user interface
+mod browser;
 /// CLI subcommand handler
 mod command_handler;
 /// CLI configuration
@@ -16,72 +20,46 @@ mod config;
 mod constants;
 /// output options
 mod display;
+/// DNSSEC key tag and DS digests
+mod dnssec;
 /// error and result wrapping
 mod errors;
+/// External subcommands found in the PATH
+mod external_command;
 /// serde helpers
 mod formatter;
+/// Commands of the CLI, and their dispatch
+mod registry;
 
-use api::dns::list::list_dns_subcommand;
-use api::dns::list_records::DnsRecordsListCommand;
-use api::dns::list_snapshots::DnsSnapshotsListCommand;
-use api::domain::check::DomainCheckCommand;
-use api::domain::list::DomainListCommand;
-use api::domain::show::DomainShowCommand;
-use api::domain::show_contacts::DomainContactsShowCommand;
-use api::domain::show_gluerecords::DomainGlueRecordsShowCommand;
-use api::organization_list::OrganizationListCommand;
-use api::user_info::UserInfoCommand;
-use command_handler::GandiSubCommandHandler;
 use config::Configuration;
 use errors::GandiResult;
+use registry::Registry;
 
-/// Parse Command line and run appropriate command.
-fn run() -> GandiResult<()> {
-    let matches = App::new(constants::NAME)
+/// Build the clap application with every commands of the registry.
+fn build_app(registry: &Registry) -> App<'static, 'static> {
+    let app = App::new(constants::NAME)
         .version(constants::VERSION)
         .author(env!("CARGO_PKG_AUTHORS"))
         .about(env!("CARGO_PKG_DESCRIPTION"))
+        .setting(AppSettings::AllowExternalSubcommands)
         .arg(
             Arg::with_name("CONFIG")
                 .short("c")
                 .long("config")
                 .takes_value(true)
                 .help("Extract Configuration from TOML file"),
-        )
-        .subcommand(
-            SubCommand::with_name("check")
-                .about("Check for domain availability")
-                .subcommand(DomainCheckCommand::subcommand()),
-        )
-        .subcommand(
-            SubCommand::with_name("show")
-                .about("Used to retrieve informations from one resource in particulary")
-                .subcommand(DomainShowCommand::subcommand())
-                .subcommand(DomainContactsShowCommand::subcommand())
-                .subcommand(DomainGlueRecordsShowCommand::subcommand())
-                .subcommand(UserInfoCommand::subcommand()),
-        )
-        .subcommand(
-            SubCommand::with_name("list")
-                .about("Used to list resources")
-                .subcommand(DomainListCommand::subcommand())
-                .subcommand(OrganizationListCommand::subcommand())
-                .subcommand(list_dns_subcommand()),
-        )
-        .get_matches();
+        );
+    registry.add_subcommands(display::add_global_options(app))
+}
 
+/// Parse Command line and run appropriate command.
+fn run() -> GandiResult<()> {
+    let registry = Registry::new();
+    let matches = build_app(&registry).get_matches();
+    display::setup(&matches);
     let config = Configuration::from(&matches);
-    DnsRecordsListCommand::handle(&config, &matches)?;
-    DnsSnapshotsListCommand::handle(&config, &matches)?;
-    DomainCheckCommand::handle(&config, &matches)?;
-    DomainShowCommand::handle(&config, &matches)?;
-    DomainContactsShowCommand::handle(&config, &matches)?;
-    DomainGlueRecordsShowCommand::handle(&config, &matches)?;
-    DomainListCommand::handle(&config, &matches)?;
-    OrganizationListCommand::handle(&config, &matches)?;
-    UserInfoCommand::handle(&config, &matches)?;
-
-    Ok(())
+    display::set_date_options(config.timezone()?, config.date_format()?);
+    registry.dispatch(&config, &matches)
 }
 
 /// Entry point of the program.
@@ -96,7 +74,7 @@ fn main() {
         }
         Err(err) => {
             let _ = eprintln!("{}", err);
-            std::process::exit(1);
+            std::process::exit(err.exit_code());
         }
     }
 }
@@ -0,0 +1,267 @@
+//! Registry of the commands of the CLI.
+//!
+//! Every command is registered with its path of subcommands, e.g.
+//! `list dns records`, the clap tree is built from the paths and the
+//! parsed command line is dispatched by walking the same path.
+//! A path may be both a command and the group of deeper commands.
+use clap::{App, AppSettings, ArgMatches, SubCommand};
+
+use super::api::dns::list_records::DnsRecordsListCommand;
+use super::api::dns::list_snapshots::DnsSnapshotsListCommand;
+use super::api::domain::check::DomainCheckCommand;
+use super::api::domain::check_domains::DomainsCheckCommand;
+use super::api::domain::check_sweep::DomainSweepCheckCommand;
+use super::api::domain::create::DomainCreateCommand;
+use super::api::domain::create_dnskey::DomainDnsKeyCreateCommand;
+use super::api::domain::create_gluerecord::DomainGlueRecordCreateCommand;
+use super::api::domain::delete_dnskey::DomainDnsKeyDeleteCommand;
+use super::api::domain::delete_gluerecord::DomainGlueRecordDeleteCommand;
+use super::api::domain::list::DomainListCommand;
+use super::api::domain::renew::DomainRenewCommand;
+use super::api::domain::show::DomainShowCommand;
+use super::api::domain::show_authinfo::DomainAuthinfoShowCommand;
+use super::api::domain::show_contacts::DomainContactsShowCommand;
+use super::api::domain::show_dnskeys::DomainDnsKeysShowCommand;
+use super::api::domain::show_gluerecords::DomainGlueRecordsShowCommand;
+use super::api::domain::show_transferlock::DomainTransferLockShowCommand;
+use super::api::domain::tags::{
+    DomainTagsAddCommand, DomainTagsRemoveCommand, DomainTagsSetCommand,
+};
+use super::api::domain::transferin::DomainTransferInCommand;
+use super::api::domain::transferin_status::DomainTransferInStatusCommand;
+use super::api::domain::update_authinfo::DomainAuthinfoUpdateCommand;
+use super::api::domain::update_autorenew::DomainAutorenewUpdateCommand;
+use super::api::domain::update_contacts::DomainContactsUpdateCommand;
+use super::api::domain::update_gluerecord::DomainGlueRecordUpdateCommand;
+use super::api::domain::update_nameservers::DomainNameserversUpdateCommand;
+use super::api::domain::update_transferlock::DomainTransferLockUpdateCommand;
+use super::api::organization_list::OrganizationListCommand;
+use super::api::user_info::UserInfoCommand;
+use super::batch;
+use super::browser;
+use super::command_handler::GandiSubCommandHandler;
+use super::config::Configuration;
+use super::errors::{GandiError, GandiResult};
+use super::external_command;
+
+/// Name of the command that display the JSON Schema of other commands.
+pub const SCHEMA_COMMAND: &str = "schema";
+
+/// A command of the CLI
+struct Command {
+    /// Subcommands names from the command line root
+    path: &'static [&'static str],
+    /// Create the clap subcommand with its arguments
+    subcommand: fn() -> App<'static, 'static>,
+    /// Display the JSON Schema of the command output, if it has one
+    schema: Option<fn() -> GandiResult<()>>,
+    /// Run the command with its parsed arguments
+    run: fn(&Configuration, &ArgMatches) -> GandiResult<()>,
+}
+
+/// A group of commands, without a command of its own
+struct Group {
+    path: &'static [&'static str],
+    about: &'static str,
+}
+
+/// The commands and groups of the CLI
+#[derive(Default)]
+pub struct Registry {
+    commands: Vec<Command>,
+    groups: Vec<Group>,
+}
+
+impl Registry {
+    /// Registry of every commands of the CLI.
+    pub fn new() -> Self {
+        let mut registry = Registry::default();
+        registry.group(&["check"], "Check for domain availability");
+        registry.handler::<DomainCheckCommand>();
+        registry.handler::<DomainsCheckCommand>();
+        registry.handler::<DomainSweepCheckCommand>();
+
+        registry.group(
+            &["show"],
+            "Used to retrieve informations from one resource in particulary",
+        );
+        registry.handler::<DomainShowCommand>();
+        registry.handler::<DomainContactsShowCommand>();
+        registry.handler::<DomainGlueRecordsShowCommand>();
+        registry.handler::<DomainDnsKeysShowCommand>();
+        registry.handler::<DomainTransferLockShowCommand>();
+        registry.handler::<DomainAuthinfoShowCommand>();
+        registry.handler::<UserInfoCommand>();
+
+        registry.group(&["list"], "Used to list resources");
+        registry.handler::<DomainListCommand>();
+        registry.handler::<OrganizationListCommand>();
+        registry.group(&["list", "dns"], "Used to list LiveDNS resources");
+        registry.handler::<DnsRecordsListCommand>();
+        registry.handler::<DnsSnapshotsListCommand>();
+
+        registry.group(&["create"], "Used to create resources");
+        registry.handler::<DomainCreateCommand>();
+        registry.handler::<DomainGlueRecordCreateCommand>();
+        registry.handler::<DomainDnsKeyCreateCommand>();
+
+        registry.group(&["renew"], "Used to renew resources");
+        registry.handler::<DomainRenewCommand>();
+
+        registry.group(&["tag"], "Used to manage the tags of the domains");
+        registry.handler::<DomainTagsAddCommand>();
+        registry.handler::<DomainTagsRemoveCommand>();
+        registry.handler::<DomainTagsSetCommand>();
+
+        registry.handler::<DomainTransferInCommand>();
+        registry.handler::<DomainTransferInStatusCommand>();
+
+        registry.group(&["update"], "Used to update resources");
+        registry.handler::<DomainAutorenewUpdateCommand>();
+        registry.handler::<DomainNameserversUpdateCommand>();
+        registry.handler::<DomainGlueRecordUpdateCommand>();
+        registry.handler::<DomainContactsUpdateCommand>();
+        registry.handler::<DomainTransferLockUpdateCommand>();
+        registry.handler::<DomainAuthinfoUpdateCommand>();
+
+        registry.group(&["delete"], "Used to delete resources");
+        registry.handler::<DomainGlueRecordDeleteCommand>();
+        registry.handler::<DomainDnsKeyDeleteCommand>();
+
+        registry.command(&[browser::COMMAND], browser::subcommand, browser::run);
+        registry.command(&[batch::COMMAND], batch::subcommand, batch::run);
+        registry
+    }
+
+    /// Describe a group of commands.
+    fn group(&mut self, path: &'static [&'static str], about: &'static str) {
+        self.groups.push(Group { path, about });
+    }
+
+    /// Register a command that is not an API handler.
+    fn command(
+        &mut self,
+        path: &'static [&'static str],
+        subcommand: fn() -> App<'static, 'static>,
+        run: fn(&Configuration, &ArgMatches) -> GandiResult<()>,
+    ) {
+        self.commands.push(Command {
+            path,
+            subcommand,
+            schema: None,
+            run,
+        });
+    }
+
+    /// Register an API handler, it has a JSON Schema.
+    fn handler<H: GandiSubCommandHandler>(&mut self) {
+        self.commands.push(Command {
+            path: H::PATH,
+            subcommand: H::command_subcommand,
+            schema: Some(H::display_schema),
+            run: H::process,
+        });
+    }
+
+    /// Find the command of the path.
+    fn find(&self, path: &[&str]) -> Option<&Command> {
+        self.commands.iter().find(|command| command.path == path)
+    }
+
+    /// Build the subcommands found under the path, or their schema
+    /// counterpart when `schema` is true.
+    fn subcommands(&self, prefix: &[&'static str], schema: bool) -> Vec<App<'static, 'static>> {
+        let mut names: Vec<&'static str> = Vec::new();
+        for command in &self.commands {
+            if schema && command.schema.is_none() {
+                continue;
+            }
+            if command.path.len() > prefix.len() && command.path.starts_with(prefix) {
+                let name = command.path[prefix.len()];
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+
+        names
+            .into_iter()
+            .map(|name| {
+                let mut path = prefix.to_vec();
+                path.push(name);
+                let children = self.subcommands(&path, schema);
+                let subcommand = match self.find(&path) {
+                    Some(_) if schema => SubCommand::with_name(name)
+                        .about("Display the JSON Schema of the command output"),
+                    // the arguments of the command are not required by its subcommands
+                    Some(command) if !children.is_empty() => {
+                        (command.subcommand)().setting(AppSettings::SubcommandsNegateReqs)
+                    }
+                    Some(command) => (command.subcommand)(),
+                    None => {
+                        let subcommand = SubCommand::with_name(name)
+                            .setting(AppSettings::SubcommandRequiredElseHelp);
+                        match self.groups.iter().find(|group| group.path == &path[..]) {
+                            Some(group) => subcommand.about(group.about),
+                            None => subcommand,
+                        }
+                    }
+                };
+                subcommand.subcommands(children)
+            })
+            .collect()
+    }
+
+    /// Add the commands to the clap application, with the schema command.
+    pub fn add_subcommands(&self, app: App<'static, 'static>) -> App<'static, 'static> {
+        app.setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommands(self.subcommands(&[], false))
+            .subcommand(
+                SubCommand::with_name(SCHEMA_COMMAND)
+                    .about("Display the JSON Schema of the output of a command")
+                    .setting(AppSettings::SubcommandRequiredElseHelp)
+                    .subcommands(self.subcommands(&[], true)),
+            )
+    }
+
+    /// Run the command matching the parsed command line,
+    /// unknown commands are external commands.
+    pub fn dispatch(&self, config: &Configuration, matches: &ArgMatches) -> GandiResult<()> {
+        let (name, params) = match matches.subcommand() {
+            (name, Some(params)) => (name, params),
+            _ => return Ok(()),
+        };
+        if name == SCHEMA_COMMAND {
+            let (path, _) = leaf(params);
+            return match self.find(&path).and_then(|command| command.schema) {
+                Some(display_schema) => display_schema(),
+                None => Err(unknown_command(&path)),
+            };
+        }
+        if !self.commands.iter().any(|command| command.path[0] == name) {
+            return external_command::run(config, matches.value_of("CONFIG"), name, params);
+        }
+        let (path, params) = leaf(matches);
+        match self.find(&path) {
+            Some(command) => (command.run)(config, params),
+            None => Err(unknown_command(&path)),
+        }
+    }
+}
+
+/// Follow the matched subcommands, returning their names and the
+/// arguments of the last one.
+fn leaf<'a>(matches: &'a ArgMatches<'a>) -> (Vec<&'a str>, &'a ArgMatches<'a>) {
+    let mut path = Vec::new();
+    let mut current = matches;
+    while let (name, Some(params)) = current.subcommand() {
+        path.push(name);
+        current = params;
+    }
+    (path, current)
+}
+
+/// Error of a path without command, clap already requires a subcommand in groups.
+fn unknown_command(path: &[&str]) -> GandiError {
+    GandiError::ClapError(format!("Unknown command {}", path.join(" ")))
+}